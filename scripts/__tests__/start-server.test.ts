@@ -1,8 +1,11 @@
 import axios from "axios";
 import fs from "node:fs";
 import path from "node:path";
+import { Readable } from "node:stream";
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
-import { loadOpenApiSpec, ValidationError } from "../../src/init-server";
+import { loadConfig } from "../../src/config";
+import { loadOpenApiSpec, specLoadOptions, specRequestHeaders, ValidationError } from "../../src/init-server";
+import { OpenAPIToMCPConverter } from "../../src/openapi/parser";
 
 // Mock fs and axios
 vi.mock("node:fs");
@@ -107,6 +110,17 @@ describe("loadOpenApiSpec", () => {
       expect(mockExit).toHaveBeenCalledWith(1);
     });
 
+    it("should report every validation problem at once", async () => {
+      const { title: _, ...info } = validOpenApiSpec.info;
+      const { paths: __, ...spec } = validOpenApiSpec;
+      vi.mocked(fs.readFileSync).mockReturnValue(JSON.stringify({ ...spec, info }));
+
+      const result = loadOpenApiSpec("./incomplete.json");
+
+      await expect(result).rejects.toBeInstanceOf(ValidationError);
+      await expect(result).rejects.toMatchObject({ errors: ["info.title is missing", "paths is missing"] });
+    });
+
     it("should load a valid OpenAPI spec from local YAML file", async () => {
       // Mock fs.readFileSync to return a valid YAML spec
       const yamlSpec = JSON.stringify(validOpenApiSpec);
@@ -132,6 +146,20 @@ describe("loadOpenApiSpec", () => {
     });
   });
 
+  describe("Stdin loading", () => {
+    it("should read the spec from stdin when the spec path is -", async () => {
+      const rawSpec = JSON.stringify(validOpenApiSpec);
+      const stdin = Readable.from([rawSpec.slice(0, 20), rawSpec.slice(20)]);
+
+      const result = await loadOpenApiSpec("-", { stdin });
+
+      expect(result).toEqual(validOpenApiSpec);
+      expect(fs.readFileSync).not.toHaveBeenCalled();
+      const { tools } = new OpenAPIToMCPConverter(result).convertToMCPTools();
+      expect(tools.API.methods.map((method) => method.name)).toEqual(["getPets"]);
+    });
+  });
+
   describe("URL loading", () => {
     it("should load a valid OpenAPI spec from URL", async () => {
       // Mock axios.get to return a valid spec
@@ -156,6 +184,102 @@ describe("loadOpenApiSpec", () => {
       expect(mockExit).toHaveBeenCalledWith(1);
     });
 
+    it("should retry the download after a transient DNS failure", async () => {
+      vi.mocked(axios.get)
+        .mockRejectedValueOnce(Object.assign(new Error("getaddrinfo EAI_AGAIN"), { code: "EAI_AGAIN" }))
+        .mockResolvedValueOnce({ data: validOpenApiSpec });
+
+      const result = await loadOpenApiSpec("http://example.com/api-spec.json", { retryDelayMs: 0 });
+
+      expect(result).toEqual(validOpenApiSpec);
+      expect(axios.get).toHaveBeenCalledTimes(2);
+    });
+
+    it("should report a network failure once retries are exhausted", async () => {
+      vi.mocked(axios.get).mockRejectedValue(
+        Object.assign(new Error("getaddrinfo EAI_AGAIN example.com"), { code: "EAI_AGAIN" }),
+      );
+      const mockExit = vi.spyOn(process, "exit").mockImplementation((() => {}) as any);
+
+      await loadOpenApiSpec("http://example.com/api-spec.json", { maxRetries: 2, retryDelayMs: 0 });
+
+      expect(axios.get).toHaveBeenCalledTimes(3);
+      expect(console.error).toHaveBeenCalledWith(
+        "Network failure while downloading OpenAPI specification after 3 attempts:",
+        "getaddrinfo EAI_AGAIN example.com",
+      );
+      expect(mockExit).toHaveBeenCalledWith(1);
+    });
+
+    it("should retry the download as often as configured", async () => {
+      vi.mocked(axios.get).mockRejectedValue(
+        Object.assign(new Error("connect ECONNREFUSED example.com"), { code: "ECONNREFUSED" }),
+      );
+      vi.spyOn(process, "exit").mockImplementation((() => {}) as any);
+      const specUrl = "http://example.com/api-spec.json";
+      const config = loadConfig({ OPENAPI_MCP_MAX_RETRIES: "1" });
+
+      await loadOpenApiSpec(specUrl, { ...specLoadOptions(config, specUrl), retryDelayMs: 0 });
+
+      expect(axios.get).toHaveBeenCalledTimes(2);
+    });
+
+    it("should not retry an unknown host", async () => {
+      vi.mocked(axios.get).mockRejectedValue(
+        Object.assign(new Error("getaddrinfo ENOTFOUND exmaple.com"), { code: "ENOTFOUND" }),
+      );
+      const mockExit = vi.spyOn(process, "exit").mockImplementation((() => {}) as any);
+
+      await loadOpenApiSpec("http://exmaple.com/api-spec.json", { maxRetries: 2, retryDelayMs: 0 });
+
+      expect(axios.get).toHaveBeenCalledTimes(1);
+      expect(console.error).toHaveBeenCalledWith(
+        "Failed to fetch OpenAPI specification from URL:",
+        "getaddrinfo ENOTFOUND exmaple.com",
+      );
+      expect(mockExit).toHaveBeenCalledWith(1);
+    });
+
+    it("should send the configured API key when fetching a protected spec", async () => {
+      vi.mocked(axios.get).mockImplementation(async (_url, options?: any) => {
+        if (options?.headers?.Authorization !== "Bearer secret") {
+          throw Object.assign(new Error("Request failed with status code 401"), { response: { status: 401 } });
+        }
+        return { data: validOpenApiSpec };
+      });
+
+      const config = loadConfig({ ANYTYPE_API_KEY: "secret", OPENAPI_MCP_SPEC_CREDENTIALS: "true" });
+      const headers = specRequestHeaders(config, "http://example.com/api-spec.json");
+      const result = await loadOpenApiSpec("http://example.com/api-spec.json", { headers });
+
+      expect(result).toEqual(validOpenApiSpec);
+      expect(axios.get).toHaveBeenCalledWith("http://example.com/api-spec.json", {
+        headers: expect.objectContaining({ Authorization: "Bearer secret" }),
+      });
+    });
+
+    it("should not send extra headers when no credentials are configured", () => {
+      expect(specRequestHeaders(loadConfig({}))).toBeUndefined();
+    });
+
+    it("should only send credentials to the origin of the API unless allowed", () => {
+      const config = loadConfig({ ANYTYPE_API_KEY: "secret", OPENAPI_MCP_SPEC_HEADERS: '{"X-Spec-Token":"t"}' });
+
+      expect(specRequestHeaders(config, "https://third-party.example.com/openapi.json")).not.toHaveProperty(
+        "Authorization",
+      );
+      expect(specRequestHeaders(config, "https://third-party.example.com/openapi.json")).toHaveProperty(
+        "X-Spec-Token",
+        "t",
+      );
+      expect(specRequestHeaders(config, "http://localhost:31009/docs/openapi.json")).toHaveProperty(
+        "Authorization",
+        "Bearer secret",
+      );
+      expect(specRequestHeaders(config)).toHaveProperty("Authorization", "Bearer secret");
+      expect(specRequestHeaders(loadConfig({ ANYTYPE_API_KEY: "secret" }), "./openapi.json")).toBeUndefined();
+    });
+
     it("should handle invalid response data", async () => {
       // Mock axios.get to return invalid data
       vi.mocked(axios.get).mockResolvedValue({ data: "invalid data" });
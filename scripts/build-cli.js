@@ -1,3 +1,4 @@
+import { execSync } from "child_process";
 import * as esbuild from "esbuild";
 import { chmod } from "fs/promises";
 import { dirname, join } from "path";
@@ -5,6 +6,15 @@ import { fileURLToPath } from "url";
 
 const __dirname = dirname(fileURLToPath(import.meta.url));
 
+// Stamped into the CLI so `anytype-mcp version` can tell which commit it was built from
+function gitCommit() {
+  try {
+    return execSync("git rev-parse --short HEAD", { encoding: "utf-8" }).trim();
+  } catch {
+    return "unknown";
+  }
+}
+
 async function build() {
   await esbuild.build({
     entryPoints: [join(__dirname, "start-server.ts")],
@@ -18,6 +28,7 @@ async function build() {
       js: "#!/usr/bin/env node\nimport { createRequire } from 'module';const require = createRequire(import.meta.url);", // see https://github.com/evanw/esbuild/pull/2067
     },
     external: ["util"],
+    define: { "process.env.ANYTYPE_MCP_GIT_COMMIT": JSON.stringify(gitCommit()) },
   });
 
   // Make the output file executable
@@ -1,19 +1,95 @@
+import { parseArgs } from "node:util";
 import { AppKeyGenerator } from "../src/auth/get-key";
-import { initProxy, loadOpenApiSpec, ValidationError } from "../src/init-server";
+import {
+  checkFlagConflicts,
+  clearToolCache,
+  listTools,
+  printSpecDiff,
+  printSpecLint,
+  printToolSchema,
+  printVersion,
+  validateSpec,
+} from "../src/commands";
+import { applyProfile, Config, DEFAULT_BASE_URL, loadConfig, loadEnvFile } from "../src/config";
+import { initProxy, loadOpenApiSpec, specLoadOptions, ValidationError } from "../src/init-server";
+import { serverBaseUrl } from "../src/mcp/proxy";
+
+const COMMANDS = [
+  "run",
+  "get-key",
+  "validate",
+  "lint-spec",
+  "list-tools",
+  "schema",
+  "diff-spec",
+  "clear-cache",
+  "version",
+];
 
 async function generateAppKey(specPath?: string) {
-  const openApiSpec = await loadOpenApiSpec(specPath);
-  const baseUrl = openApiSpec.servers?.[0]?.url || "http://localhost:31009";
-  const generator = new AppKeyGenerator(baseUrl);
+  const config = loadConfig();
+  const openApiSpec = await loadOpenApiSpec(specPath, specLoadOptions(config, specPath));
+  const baseUrl = serverBaseUrl(openApiSpec, config.serverVariables) || config.defaultBaseUrl || DEFAULT_BASE_URL;
+  const generator = new AppKeyGenerator(baseUrl, config.healthPath);
   await generator.generateAppKey();
 }
 
 export async function main(args: string[] = process.argv.slice(2)) {
-  const [command, specPath] = args;
-  if (!command || command === "run") {
-    await initProxy(specPath);
+  const { values, positionals } = parseArgs({
+    args,
+    allowPositionals: true,
+    options: {
+      json: { type: "boolean", default: false },
+      strict: { type: "boolean", default: false },
+      verbose: { type: "boolean", default: false },
+      method: { type: "string" },
+      pretty: { type: "boolean" },
+      compact: { type: "boolean" },
+      "env-file": { type: "string" },
+      profile: { type: "string" },
+      version: { type: "boolean", default: false },
+    },
+  });
+  const [command = values.version ? "version" : "run", specPath] = positionals;
+  if (values["env-file"]) {
+    loadEnvFile(values["env-file"]);
+  }
+  if (values.profile) {
+    applyProfile(values.profile);
+  }
+  if (COMMANDS.includes(command)) {
+    checkFlagConflicts(command, values);
+  }
+  if (command === "run") {
+    const overrides: Partial<Config> = {};
+    if (values.pretty || values.compact) {
+      overrides.prettyResults = Boolean(values.pretty);
+    }
+    await initProxy(specPath, overrides);
   } else if (command === "get-key") {
     await generateAppKey(specPath);
+  } else if (command === "validate") {
+    await validateSpec(specPath, Boolean(values.json), Boolean(values.strict));
+  } else if (command === "lint-spec") {
+    await printSpecLint(specPath);
+  } else if (command === "list-tools") {
+    await listTools(specPath, { verbose: Boolean(values.verbose), method: values.method });
+  } else if (command === "diff-spec") {
+    const [, oldSpecPath, newSpecPath] = positionals;
+    if (!oldSpecPath || !newSpecPath) {
+      throw new Error("diff-spec needs two specs: anytype-mcp diff-spec <old-spec> <new-spec>");
+    }
+    await printSpecDiff(oldSpecPath, newSpecPath);
+  } else if (command === "version") {
+    await printVersion(specPath);
+  } else if (command === "clear-cache") {
+    clearToolCache();
+  } else if (command === "schema") {
+    const [, toolName, schemaSpecPath] = positionals;
+    if (!toolName) {
+      throw new Error("schema needs a tool name: anytype-mcp schema <tool-name> [spec-path]");
+    }
+    await printToolSchema(schemaSpecPath, toolName);
   } else {
     console.error(`Error: Unknown command "${command}"`);
     process.exit(1);
@@ -22,8 +98,8 @@ export async function main(args: string[] = process.argv.slice(2)) {
 
 main().catch((error) => {
   if (error instanceof ValidationError) {
-    console.error("Invalid OpenAPI 3.1 specification:");
-    error.errors.forEach((err) => console.error(err));
+    console.error("Invalid OpenAPI specification:");
+    error.errors.forEach((err) => console.error(`  - ${err}`));
   } else {
     console.error("Error:", error.message);
   }
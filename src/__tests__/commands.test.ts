@@ -0,0 +1,299 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { OpenAPIV3 } from "openapi-types";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import packageJson from "../../package.json";
+import {
+  checkFlagConflicts,
+  diffSpecs,
+  lintSpec,
+  listTools,
+  printSpecDiff,
+  printSpecLint,
+  printToolSchema,
+  printVersion,
+  validateSpec,
+} from "../commands";
+import { loadOpenApiSpec, specLoadOptions } from "../init-server";
+
+vi.mock("../init-server");
+vi.mock("../client/http-client");
+vi.mock("@modelcontextprotocol/sdk/server/index.js");
+
+const spec: OpenAPIV3.Document = {
+  openapi: "3.0.0",
+  servers: [{ url: "http://localhost:3000" }],
+  info: { title: "Test API", version: "1.0.0" },
+  paths: {
+    "/spaces": {
+      get: {
+        operationId: "listSpaces",
+        summary: "List spaces",
+        responses: { "200": { description: "OK" } },
+      },
+      post: {
+        operationId: "createSpace",
+        summary: "Create a space",
+        responses: { "201": { description: "Created" } },
+      },
+    },
+  },
+};
+
+function captureOutput() {
+  const logSpy = vi.spyOn(console, "log").mockImplementation(() => {});
+  return () => logSpy.mock.calls.map((args) => args.join(" ")).join("\n");
+}
+
+describe("commands", () => {
+  beforeEach(() => {
+    vi.restoreAllMocks();
+    vi.mocked(loadOpenApiSpec).mockResolvedValue(spec);
+  });
+
+  describe("listTools", () => {
+    it("should print only tool names by default", async () => {
+      const output = captureOutput();
+
+      await listTools(undefined);
+
+      expect(output()).toBe("API-listSpaces\nAPI-createSpace");
+    });
+
+    it("should print method, path and description with verbose", async () => {
+      const output = captureOutput();
+
+      await listTools(undefined, { verbose: true });
+
+      const lines = output().split("\n");
+      expect(lines[0]).toMatch(/^METHOD\s+PATH\s+TOOL\s+DESCRIPTION$/);
+      expect(lines[1]).toMatch(/^GET\s+\/spaces\s+API-listSpaces\s+List spaces$/);
+      expect(lines[2]).toMatch(/^POST\s+\/spaces\s+API-createSpace\s+Create a space$/);
+    });
+
+    it("should only print tools for the requested HTTP method", async () => {
+      const output = captureOutput();
+
+      await listTools(undefined, { method: "get" });
+
+      expect(output()).toBe("API-listSpaces");
+    });
+  });
+
+  describe("validateSpec", () => {
+    const collidingSpec: OpenAPIV3.Document = {
+      ...spec,
+      paths: {
+        ...spec.paths,
+        "/spaces/{space_id}": {
+          get: { operationId: "listSpaces", responses: { "200": { description: "OK" } } },
+        },
+        "spaces/{id}/": {
+          get: { operationId: "getSpace", responses: { "200": { description: "OK" } } },
+        },
+      },
+    };
+
+    it("should warn about duplicate operationIds and colliding routes", async () => {
+      vi.mocked(loadOpenApiSpec).mockResolvedValue(collidingSpec);
+      const output = captureOutput();
+
+      await validateSpec(undefined, false);
+
+      expect(output()).toContain("Warning: found 2 collision(s):");
+      expect(output()).toContain("  - operationId listSpaces is used by GET /spaces, GET /spaces/{space_id}");
+      expect(output()).toContain("  - GET /spaces/{space_id}, GET spaces/{id}/ resolve to the same route");
+    });
+
+    it("should fail on collisions in strict mode", async () => {
+      vi.mocked(loadOpenApiSpec).mockResolvedValue(collidingSpec);
+      captureOutput();
+
+      await expect(validateSpec(undefined, false, true)).rejects.toThrow("Spec has 2 colliding operation(s)");
+    });
+  });
+
+  describe("printToolSchema", () => {
+    it("should print the input schema of the tool as JSON", async () => {
+      vi.mocked(loadOpenApiSpec).mockResolvedValue({
+        ...spec,
+        paths: {
+          "/spaces/{space_id}": {
+            get: {
+              operationId: "getSpace",
+              parameters: [{ name: "space_id", in: "path", required: true, schema: { type: "string" } }],
+              responses: { "200": { description: "OK" } },
+            },
+          },
+        },
+      });
+      const output = captureOutput();
+
+      await printToolSchema(undefined, "API-getSpace");
+
+      const schema = JSON.parse(output());
+      expect(schema.type).toBe("object");
+      expect(Object.keys(schema.properties)).toEqual(["space_id"]);
+    });
+
+    it("should fail for an unknown tool", async () => {
+      await expect(printToolSchema(undefined, "API-missing")).rejects.toThrow('Unknown tool "API-missing"');
+    });
+  });
+
+  describe("diffSpecs", () => {
+    const newSpec: OpenAPIV3.Document = {
+      ...spec,
+      paths: {
+        "/spaces": { get: spec.paths["/spaces"]!.get },
+        "/spaces/{spaceId}": {
+          get: {
+            operationId: "getSpace",
+            parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    it("should report added and removed tools", () => {
+      const diff = diffSpecs(spec, newSpec, {});
+
+      expect(diff.added).toEqual(["API-getSpace (GET /spaces/{spaceId})"]);
+      expect(diff.removed).toEqual(["API-createSpace (POST /spaces)"]);
+      expect(diff.changed).toEqual([]);
+    });
+
+    it("should report tools whose route changed", async () => {
+      const moved: OpenAPIV3.Document = { ...spec, paths: { "/v2/spaces": spec.paths["/spaces"] } };
+      vi.mocked(loadOpenApiSpec).mockResolvedValueOnce(spec).mockResolvedValueOnce(moved);
+      const output = captureOutput();
+
+      await printSpecDiff("old.json", "new.json");
+
+      expect(output().split("\n")).toEqual([
+        "Changed (2):",
+        "  ~ API-listSpaces: GET /spaces -> GET /v2/spaces",
+        "  ~ API-createSpace: POST /spaces -> POST /v2/spaces",
+      ]);
+    });
+  });
+
+  describe("lintSpec", () => {
+    const sloppySpec: OpenAPIV3.Document = {
+      ...spec,
+      paths: {
+        "/spaces": {
+          get: { operationId: "listSpaces", summary: "List spaces", responses: { "200": { description: "OK" } } },
+          post: { responses: { "201": { description: "Created" } } },
+        },
+        "/spaces/{spaceId}": {
+          delete: { description: "Delete a space", responses: {} },
+        },
+      },
+    };
+
+    it("should report missing operationIds and descriptions", () => {
+      const lint = lintSpec(sloppySpec);
+
+      expect(lint.missingOperationIds).toEqual(["POST /spaces", "DELETE /spaces/{spaceId}"]);
+      expect(lint.missingDescriptions).toEqual(["POST /spaces"]);
+      expect(lint.missingResponses).toEqual(["DELETE /spaces/{spaceId}"]);
+    });
+
+    it("should print the findings with their counts", async () => {
+      vi.mocked(loadOpenApiSpec).mockResolvedValueOnce(sloppySpec);
+      const output = captureOutput();
+
+      await printSpecLint("./spec.json");
+
+      expect(output().split("\n")).toEqual([
+        "Missing operationId (2):",
+        "  - POST /spaces",
+        "  - DELETE /spaces/{spaceId}",
+        "Missing description (1):",
+        "  - POST /spaces",
+        "No declared responses (1):",
+        "  - DELETE /spaces/{spaceId}",
+        "",
+        "4 finding(s), none of them fatal",
+      ]);
+    });
+
+    it("should report a clean spec", async () => {
+      const output = captureOutput();
+
+      await printSpecLint(undefined);
+
+      expect(output()).toBe("No issues found");
+    });
+  });
+
+  describe("tool cache", () => {
+    it("should not write the tool cache from read-only commands", async () => {
+      const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+      const cachePath = path.join(dir, "tools.json");
+      vi.stubEnv("OPENAPI_MCP_TOOL_CACHE", cachePath);
+      captureOutput();
+
+      try {
+        await validateSpec(undefined, false);
+        await listTools(undefined);
+
+        expect(fs.existsSync(cachePath)).toBe(false);
+      } finally {
+        vi.unstubAllEnvs();
+        fs.rmSync(dir, { recursive: true });
+      }
+    });
+  });
+
+  describe("protected specs", () => {
+    it("should load the spec with the configured headers and retries", async () => {
+      const options = { headers: { Authorization: "Bearer secret" }, maxRetries: 1 };
+      vi.mocked(specLoadOptions).mockReturnValueOnce(options).mockReturnValueOnce(options);
+      captureOutput();
+
+      await listTools("https://api.example.com/openapi.json");
+      expect(loadOpenApiSpec).toHaveBeenLastCalledWith("https://api.example.com/openapi.json", options);
+
+      await printSpecLint("https://api.example.com/openapi.json");
+      expect(loadOpenApiSpec).toHaveBeenLastCalledWith("https://api.example.com/openapi.json", options);
+    });
+  });
+
+  describe("printVersion", () => {
+    it("should print the package version, and the spec version when given a spec", async () => {
+      const output = captureOutput();
+
+      await printVersion("./spec.json");
+
+      expect(output()).toContain(`anytype-mcp ${packageJson.version}`);
+      expect(output()).toContain(`node ${process.version}`);
+      expect(output()).toContain("spec Test API 1.0.0");
+    });
+  });
+
+  describe("checkFlagConflicts", () => {
+    it("should reject contradictory output flags", () => {
+      expect(() => checkFlagConflicts("run", { pretty: true, compact: true })).toThrow(
+        "--pretty and --compact cannot be used together, pick one",
+      );
+    });
+
+    it("should reject flags the command would ignore", () => {
+      expect(() => checkFlagConflicts("run", { method: "GET" })).toThrow(
+        '--method only applies to list-tools, not "run"',
+      );
+      expect(() => checkFlagConflicts("list-tools", { json: true })).toThrow(
+        '--json only applies to validate, not "list-tools"',
+      );
+    });
+
+    it("should accept flags that apply to the command", () => {
+      expect(() => checkFlagConflicts("list-tools", { verbose: true, method: "GET" })).not.toThrow();
+      expect(() => checkFlagConflicts("run", { pretty: true, json: false })).not.toThrow();
+    });
+  });
+});
@@ -0,0 +1,138 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { describe, expect, it, vi } from "vitest";
+import { getDefaultHeaders } from "../client/http-client";
+import { applyProfile, ConfigError, loadConfig, loadConfigStrict, loadEnvFile } from "../config";
+
+describe("loadConfig", () => {
+  it("should return an empty config when no variables are set", () => {
+    expect(loadConfig({})).toEqual({});
+  });
+
+  it("should parse basic auth credentials", () => {
+    const config = loadConfig({ OPENAPI_MCP_BASIC_AUTH: "user:p@ss:word" });
+
+    expect(config.basicAuth).toEqual({ username: "user", password: "p@ss:word" });
+  });
+
+  it("should ignore malformed basic auth credentials", () => {
+    const consoleSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+
+    const config = loadConfig({ OPENAPI_MCP_BASIC_AUTH: "user" });
+
+    expect(config.basicAuth).toBeUndefined();
+    expect(consoleSpy).toHaveBeenCalled();
+    consoleSpy.mockRestore();
+  });
+
+  it("should parse OAuth client credentials and reject incomplete ones", () => {
+    const config = loadConfig({
+      OPENAPI_MCP_OAUTH_TOKEN_URL: "https://auth.example.com/token",
+      OPENAPI_MCP_OAUTH_CLIENT_ID: "id",
+      OPENAPI_MCP_OAUTH_CLIENT_SECRET: "secret",
+      OPENAPI_MCP_OAUTH_SCOPES: "objects:read,objects:write",
+    });
+
+    expect(config.oauth).toEqual({
+      tokenUrl: "https://auth.example.com/token",
+      clientId: "id",
+      clientSecret: "secret",
+      scopes: ["objects:read", "objects:write"],
+    });
+    expect(() => loadConfigStrict({ OPENAPI_MCP_OAUTH_CLIENT_ID: "id" })).toThrow(ConfigError);
+  });
+
+  it("should parse a comma-separated list of case-sensitive headers", () => {
+    const config = loadConfig({ OPENAPI_MCP_CASE_SENSITIVE_HEADERS: "X-AnyType-Client, ETag ," });
+
+    expect(config.caseSensitiveHeaders).toEqual(["X-AnyType-Client", "ETag"]);
+  });
+
+  it("should read headers from a JSON file", () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+    const headersFile = path.join(dir, "headers.json");
+    fs.writeFileSync(headersFile, JSON.stringify({ "Anytype-Version": "2025-05-20", "X-Team": "docs" }));
+
+    try {
+      const config = loadConfig({ OPENAPI_MCP_HEADERS_FILE: headersFile });
+
+      expect(getDefaultHeaders({ headers: config.headers })).toMatchObject({
+        "Anytype-Version": "2025-05-20",
+        "X-Team": "docs",
+      });
+    } finally {
+      fs.rmSync(dir, { recursive: true });
+    }
+  });
+
+  it("should report malformed variables as an error in strict mode", () => {
+    const env = { OPENAPI_MCP_MAX_RESULT_CHARS: "ten", OPENAPI_MCP_ETAG_CACHE: "maybe" };
+
+    expect(() => loadConfigStrict(env)).toThrow(ConfigError);
+    expect(() => loadConfigStrict(env)).toThrow(
+      'Invalid configuration:\n  - OPENAPI_MCP_ETAG_CACHE must be a boolean (true or false), got "maybe"\n' +
+        '  - OPENAPI_MCP_MAX_RESULT_CHARS must be a positive integer, got "ten"',
+    );
+    expect(loadConfigStrict({ OPENAPI_MCP_MAX_RESULT_CHARS: "10" })).toEqual({ maxResultChars: 10 });
+  });
+
+  it("should ignore malformed variables outside strict mode", () => {
+    const consoleSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+
+    const config = loadConfig({ OPENAPI_MCP_MAX_RESULT_CHARS: "ten" });
+
+    expect(config.maxResultChars).toBeUndefined();
+    expect(consoleSpy).toHaveBeenCalledWith(
+      'OPENAPI_MCP_MAX_RESULT_CHARS must be a positive integer, got "ten", ignoring it',
+    );
+    consoleSpy.mockRestore();
+  });
+});
+
+describe("loadEnvFile", () => {
+  it("should load variables from a dotenv file without overriding set ones", () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+    const envFile = path.join(dir, ".env");
+    const lines = [
+      "# local setup",
+      "export ANYTYPE_API_KEY='from-file'",
+      "OPENAPI_MCP_ETAG_CACHE=true # cache GETs",
+      "",
+      "OPENAPI_MCP_WARMUP=true",
+    ];
+    fs.writeFileSync(envFile, lines.join("\n"));
+    const env: NodeJS.ProcessEnv = { OPENAPI_MCP_WARMUP: "false" };
+
+    try {
+      loadEnvFile(envFile, env);
+    } finally {
+      fs.rmSync(dir, { recursive: true });
+    }
+
+    expect(loadConfig(env)).toEqual({ apiKey: "from-file", etagCache: true, warmupOnStart: false });
+  });
+});
+
+describe("applyProfile", () => {
+  const env = {
+    ANYTYPE_API_KEY: "shared-key",
+    LOCAL__OPENAPI_MCP_DEFAULT_BASE_URL: "http://localhost:31009",
+    CLOUD__OPENAPI_MCP_DEFAULT_BASE_URL: "https://anytype.example.com",
+  };
+
+  it("should override the base config with the variables of the selected profile", () => {
+    const local: NodeJS.ProcessEnv = { ...env };
+    const cloud: NodeJS.ProcessEnv = { ...env };
+
+    applyProfile("local", local);
+    applyProfile("cloud", cloud);
+
+    expect(loadConfig(local)).toMatchObject({ apiKey: "shared-key", defaultBaseUrl: "http://localhost:31009" });
+    expect(loadConfig(cloud)).toMatchObject({ apiKey: "shared-key", defaultBaseUrl: "https://anytype.example.com" });
+  });
+
+  it("should reject a profile without variables", () => {
+    expect(() => applyProfile("staging", { ...env })).toThrow('Profile "staging" has no variables');
+  });
+});
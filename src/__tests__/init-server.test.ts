@@ -0,0 +1,77 @@
+import fs from "node:fs";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { HttpClient } from "../client/http-client";
+import { initProxy, loadOpenApiSpec, resolveSpecSource } from "../init-server";
+
+vi.mock("node:fs");
+vi.mock("../client/http-client");
+vi.mock("@modelcontextprotocol/sdk/server/index.js");
+vi.mock("@modelcontextprotocol/sdk/server/stdio.js");
+
+const spec = {
+  openapi: "3.0.0",
+  servers: [{ url: "http://localhost:31009" }],
+  info: { title: "Test API", version: "1.0.0" },
+  paths: {
+    "/spaces": {
+      get: { operationId: "listSpaces", responses: { "200": { description: "OK" } } },
+    },
+  },
+};
+
+describe("initProxy", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    vi.mocked(fs.readFileSync).mockReturnValue(JSON.stringify(spec));
+    vi.stubEnv("ANYTYPE_API_KEY", "");
+    vi.stubEnv("OPENAPI_MCP_BASIC_AUTH", "");
+    vi.stubEnv("OPENAPI_MCP_SPEC_HEADERS", "");
+    vi.spyOn(console, "error").mockImplementation(() => {});
+  });
+
+  afterEach(() => {
+    vi.unstubAllEnvs();
+  });
+
+  it("should warm up the connection before serving when enabled", async () => {
+    vi.stubEnv("OPENAPI_MCP_WARMUP", "true");
+
+    await initProxy("./spec.json");
+
+    expect(HttpClient.prototype.warmup).toHaveBeenCalledTimes(1);
+  });
+
+  it("should not warm up the connection by default", async () => {
+    vi.stubEnv("OPENAPI_MCP_WARMUP", "");
+
+    await initProxy("./spec.json");
+
+    expect(HttpClient.prototype.warmup).not.toHaveBeenCalled();
+  });
+
+  it("should refuse to read the spec from stdin, which serves MCP", async () => {
+    await expect(initProxy("-")).rejects.toThrow("Cannot read the spec from stdin when running the server");
+
+    expect(fs.readFileSync).not.toHaveBeenCalled();
+  });
+});
+
+describe("resolveSpecSource", () => {
+  it("should record the resolved path of a local spec", async () => {
+    vi.mocked(fs.readFileSync).mockReturnValue(JSON.stringify(spec));
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    const expected = path.resolve(process.cwd(), "./spec.json");
+
+    await loadOpenApiSpec("./spec.json");
+
+    expect(resolveSpecSource("./spec.json")).toEqual({ kind: "file", location: expected });
+    expect(fs.readFileSync).toHaveBeenLastCalledWith(expected, "utf-8");
+    expect(consoleSpy).toHaveBeenCalledWith(`Loading OpenAPI specification from file ${expected}`);
+  });
+
+  it("should fall back to the spec served by the local app", () => {
+    expect(resolveSpecSource()).toEqual({ kind: "default-url", location: "http://localhost:31009/docs/openapi.json" });
+    expect(resolveSpecSource("-")).toEqual({ kind: "stdin", location: "-" });
+  });
+});
@@ -0,0 +1,35 @@
+import axios from "axios";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { AppKeyGenerator } from "../get-key";
+
+vi.mock("axios");
+vi.mock("readline");
+
+describe("AppKeyGenerator", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  describe("testConnection", () => {
+    it("should request the configured health path", async () => {
+      vi.mocked(axios.get).mockResolvedValue({ status: 200, data: {} });
+      const generator = new AppKeyGenerator("http://localhost:31009", "/v1/ping");
+
+      await expect(generator.testConnection()).resolves.toBe(true);
+
+      expect(axios.get).toHaveBeenCalledWith("http://localhost:31009/v1/ping", {
+        validateStatus: expect.any(Function),
+      });
+    });
+
+    it("should report an unreachable API", async () => {
+      vi.spyOn(console, "error").mockImplementation(() => {});
+      vi.mocked(axios.get).mockRejectedValue(new Error("connect ECONNREFUSED 127.0.0.1:31009"));
+      const generator = new AppKeyGenerator("http://localhost:31009");
+
+      await expect(generator.testConnection()).resolves.toBe(false);
+
+      expect(axios.get).toHaveBeenCalledWith("http://localhost:31009/health", expect.anything());
+    });
+  });
+});
@@ -0,0 +1,66 @@
+import axios from "axios";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { ClientCredentialsTokenSource, TOKEN_REFRESH_MARGIN_MS } from "../oauth";
+
+vi.mock("axios");
+
+const credentials = {
+  tokenUrl: "https://auth.example.com/oauth/token",
+  clientId: "anytype-mcp",
+  clientSecret: "s3cret",
+  scopes: ["objects:read", "objects:write"],
+};
+
+describe("ClientCredentialsTokenSource", () => {
+  let now: number;
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    now = 1_000_000;
+  });
+
+  it("should fetch a token with the client credentials grant", async () => {
+    vi.mocked(axios.post).mockResolvedValueOnce({ data: { access_token: "token-1", expires_in: 3600 } });
+    const source = new ClientCredentialsTokenSource(credentials, () => now);
+
+    await expect(source.getToken()).resolves.toBe("token-1");
+
+    expect(axios.post).toHaveBeenCalledWith(
+      "https://auth.example.com/oauth/token",
+      "grant_type=client_credentials&client_id=anytype-mcp&client_secret=s3cret&scope=objects%3Aread+objects%3Awrite",
+      { headers: { "Content-Type": "application/x-www-form-urlencoded", Accept: "application/json" } },
+    );
+  });
+
+  it("should reuse the cached token until shortly before it expires", async () => {
+    vi.mocked(axios.post)
+      .mockResolvedValueOnce({ data: { access_token: "token-1", expires_in: 3600 } })
+      .mockResolvedValueOnce({ data: { access_token: "token-2", expires_in: 3600 } });
+    const source = new ClientCredentialsTokenSource(credentials, () => now);
+
+    await source.getToken();
+    now += 3600 * 1000 - TOKEN_REFRESH_MARGIN_MS - 1;
+    await expect(source.getToken()).resolves.toBe("token-1");
+    expect(axios.post).toHaveBeenCalledTimes(1);
+
+    now += 1;
+    await expect(source.getToken()).resolves.toBe("token-2");
+    expect(axios.post).toHaveBeenCalledTimes(2);
+  });
+
+  it("should share one token request between concurrent callers", async () => {
+    vi.mocked(axios.post).mockResolvedValueOnce({ data: { access_token: "token-1", expires_in: 3600 } });
+    const source = new ClientCredentialsTokenSource(credentials, () => now);
+
+    await expect(Promise.all([source.getToken(), source.getToken()])).resolves.toEqual(["token-1", "token-1"]);
+
+    expect(axios.post).toHaveBeenCalledTimes(1);
+  });
+
+  it("should fail when the token endpoint returns no access token", async () => {
+    vi.mocked(axios.post).mockResolvedValueOnce({ data: { error: "invalid_client" } });
+    const source = new ClientCredentialsTokenSource(credentials, () => now);
+
+    await expect(source.getToken()).rejects.toThrow("returned no access_token");
+  });
+});
@@ -1,5 +1,6 @@
 import axios from "axios";
 import * as readline from "readline";
+import { DEFAULT_HEALTH_PATH } from "../config";
 
 interface AuthToken {
   app_key: string;
@@ -9,9 +10,11 @@ export class AppKeyGenerator {
   private readonly rl: readline.Interface;
   private readonly appName: string = "anytype_mcp_server";
   private readonly basePath: string;
+  private readonly healthPath: string;
 
-  constructor(basePath: string) {
+  constructor(basePath: string, healthPath: string = DEFAULT_HEALTH_PATH) {
     this.basePath = basePath;
+    this.healthPath = healthPath;
     this.rl = readline.createInterface({
       input: process.stdin,
       output: process.stdout,
@@ -45,6 +48,20 @@ export class AppKeyGenerator {
 `);
   }
 
+  /**
+   * Check that the API is reachable by requesting its health path. Any HTTP response counts,
+   * only a failure to connect does not.
+   */
+  public async testConnection(): Promise<boolean> {
+    try {
+      await axios.get(`${this.basePath}${this.healthPath}`, { validateStatus: () => true });
+      return true;
+    } catch (error) {
+      console.error("Connection error:", error instanceof Error ? error.message : error);
+      return false;
+    }
+  }
+
   /**
    * Start the authentication process with Anytype
    * @returns Challenge ID to use with completeAuthentication
@@ -94,6 +111,9 @@ export class AppKeyGenerator {
 
   public async generateAppKey(): Promise<void> {
     try {
+      if (!(await this.testConnection())) {
+        throw new Error(`Can't connect to API at ${this.basePath}. Please ensure Anytype is running and reachable.`);
+      }
       console.log("Starting authentication to get app key...");
 
       const challengeId = await this.startAuthentication();
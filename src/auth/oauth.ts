@@ -0,0 +1,57 @@
+import axios from "axios";
+import type { OAuthClientCredentials } from "../config";
+
+interface TokenResponse {
+  access_token: string;
+  expires_in?: number;
+}
+
+// A token is fetched again this long before it expires, so requests in flight don't carry an expired one
+export const TOKEN_REFRESH_MARGIN_MS = 30_000;
+
+/**
+ * Fetches bearer tokens with the OAuth2 client credentials grant and reuses each one until shortly
+ * before it expires. A token without expires_in is reused for as long as the server runs.
+ */
+export class ClientCredentialsTokenSource {
+  private token?: { value: string; expiresAt: number };
+  private pending?: Promise<string>;
+
+  constructor(
+    private readonly credentials: OAuthClientCredentials,
+    private readonly now: () => number = Date.now,
+  ) {}
+
+  async getToken(): Promise<string> {
+    if (this.token && this.now() < this.token.expiresAt - TOKEN_REFRESH_MARGIN_MS) {
+      return this.token.value;
+    }
+    // Concurrent requests share one token request
+    this.pending ??= this.fetchToken().finally(() => {
+      this.pending = undefined;
+    });
+    return this.pending;
+  }
+
+  private async fetchToken(): Promise<string> {
+    const { tokenUrl, clientId, clientSecret, scopes } = this.credentials;
+    const form = new URLSearchParams({
+      grant_type: "client_credentials",
+      client_id: clientId,
+      client_secret: clientSecret,
+    });
+    if (scopes && scopes.length > 0) {
+      form.set("scope", scopes.join(" "));
+    }
+    const response = await axios.post<TokenResponse>(tokenUrl, form.toString(), {
+      headers: { "Content-Type": "application/x-www-form-urlencoded", Accept: "application/json" },
+    });
+    const { access_token, expires_in } = response.data;
+    if (!access_token) {
+      throw new Error(`Token endpoint ${tokenUrl} returned no access_token`);
+    }
+    const expiresAt = typeof expires_in === "number" ? this.now() + expires_in * 1000 : Infinity;
+    this.token = { value: access_token, expiresAt };
+    return access_token;
+  }
+}
@@ -0,0 +1,65 @@
+import type FormData from "form-data";
+import fs from "fs";
+import { OpenAPIV3 } from "openapi-types";
+import os from "os";
+import path from "path";
+import { Writable } from "stream";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { HttpClient } from "../http-client";
+
+describe("HttpClient streamed file upload", () => {
+  const fileSize = 4 * 1024 * 1024;
+  let dir: string;
+  let filePath: string;
+
+  const operation = {
+    operationId: "uploadFile",
+    method: "post",
+    path: "/upload",
+    requestBody: {
+      content: {
+        "multipart/form-data": {
+          schema: { type: "object", properties: { file: { type: "string", format: "binary" } } },
+        },
+      },
+    },
+    responses: { "200": { description: "OK" } },
+  } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-upload-"));
+    filePath = path.join(dir, "export.bin");
+    fs.writeFileSync(filePath, Buffer.alloc(fileSize, 1));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true });
+    vi.restoreAllMocks();
+  });
+
+  it("streams a file from its path in chunks instead of reading it into memory", async () => {
+    const readFileSpy = vi.spyOn(fs, "readFileSync");
+    const chunkSizes: number[] = [];
+    const uploadFile = vi.fn(async (_params: unknown, formData: FormData) => {
+      const sink = new Writable({
+        write(chunk: Buffer, _encoding, callback) {
+          chunkSizes.push(chunk.length);
+          callback();
+        },
+      });
+      await new Promise((resolve, reject) => formData.pipe(sink).on("finish", resolve).on("error", reject));
+      return { data: { success: true }, status: 200, headers: {} };
+    });
+    const spec: OpenAPIV3.Document = { openapi: "3.0.0", info: { title: "Test API", version: "1.0.0" }, paths: {} };
+    const client = new HttpClient({ baseUrl: "http://test.com" }, spec);
+    client["api"] = Promise.resolve({ uploadFile } as any);
+
+    await client.executeOperation(operation, { file: filePath });
+
+    const transferred = chunkSizes.reduce((sum, size) => sum + size, 0);
+    expect(transferred).toBeGreaterThan(fileSize);
+    expect(Math.max(...chunkSizes)).toBeLessThan(fileSize);
+    expect(readFileSpy).not.toHaveBeenCalledWith(filePath, expect.anything());
+    expect(readFileSpy).not.toHaveBeenCalledWith(filePath);
+  });
+});
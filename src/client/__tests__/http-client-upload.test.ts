@@ -204,4 +204,72 @@ describe("HttpClient File Upload", () => {
     expect(FormData.prototype.append).toHaveBeenCalledWith("description", "Test files");
     expect(mockApiInstance.uploadFile).toHaveBeenCalledWith({}, expect.any(FormData), { headers: mockFormDataHeaders });
   });
+
+  it("should add each entry in _files as a separate multipart part", async () => {
+    const mockFormDataHeaders = { "content-type": "multipart/form-data; boundary=---123" };
+    vi.mocked(FormData.prototype.append).mockImplementation(() => {});
+    vi.mocked(FormData.prototype.getHeaders).mockReturnValue(mockFormDataHeaders);
+
+    const operation = mockOpenApiSpec.paths["/upload"]?.post as OpenAPIV3.OperationObject & {
+      method: string;
+      path: string;
+    };
+    const params = {
+      file: "/path/to/test.txt",
+      _files: [
+        {
+          field: "attachment",
+          filename: "notes.txt",
+          data: Buffer.from("hello").toString("base64"),
+          content_type: "text/plain",
+        },
+        {
+          field: "cover",
+          filename: "cover.png",
+          data: Buffer.from("png-bytes").toString("base64"),
+          content_type: "image/png",
+        },
+      ],
+    };
+
+    mockApiInstance.uploadFile.mockResolvedValue({
+      data: { success: true },
+      status: 200,
+      headers: {},
+    });
+
+    await client.executeOperation(operation, params);
+
+    expect(FormData.prototype.append).toHaveBeenCalledWith("attachment", Buffer.from("hello"), {
+      filename: "notes.txt",
+      contentType: "text/plain",
+    });
+    expect(FormData.prototype.append).toHaveBeenCalledWith("cover", Buffer.from("png-bytes"), {
+      filename: "cover.png",
+      contentType: "image/png",
+    });
+    expect(FormData.prototype.append).not.toHaveBeenCalledWith("_files", expect.anything());
+  });
+
+  it("should add _json_part as a part with a JSON content type", async () => {
+    vi.mocked(FormData.prototype.append).mockImplementation(() => {});
+    vi.mocked(FormData.prototype.getHeaders).mockReturnValue({
+      "content-type": "multipart/form-data; boundary=---123",
+    });
+    mockApiInstance.uploadFile.mockResolvedValue({ data: { success: true }, status: 200, headers: {} });
+    const operation = mockOpenApiSpec.paths["/upload"]?.post as OpenAPIV3.OperationObject & {
+      method: string;
+      path: string;
+    };
+
+    await client.executeOperation(operation, {
+      file: "/path/to/test.txt",
+      _json_part: { field: "metadata", value: { name: "Report", tags: ["q1"] } },
+    });
+
+    expect(FormData.prototype.append).toHaveBeenCalledWith("metadata", '{"name":"Report","tags":["q1"]}', {
+      contentType: "application/json",
+    });
+    expect(FormData.prototype.append).not.toHaveBeenCalledWith("_json_part", expect.anything());
+  });
 });
@@ -0,0 +1,67 @@
+import axios, { type AxiosAdapter } from "axios";
+import { OpenAPIV3 } from "openapi-types";
+import { describe, expect, it } from "vitest";
+import { HttpClient } from "../http-client";
+
+const spec: OpenAPIV3.Document = {
+  openapi: "3.0.0",
+  info: { title: "Test API", version: "1.0.0" },
+  paths: {
+    "/spaces/{spaceId}": {
+      get: {
+        operationId: "getSpace",
+        parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+        responses: { "200": { description: "OK" } },
+      },
+    },
+    "spaces/{spaceId}/members": {
+      get: {
+        operationId: "listMembers",
+        parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+        responses: { "200": { description: "OK" } },
+      },
+    },
+  },
+};
+
+// A client built on the real openapi-client-axios, whose requests end in a stub recording their final URL
+function recordingClient() {
+  const urls: string[] = [];
+  const adapter: AxiosAdapter = async (config) => {
+    urls.push(axios.getUri(config));
+    return { data: "{}", status: 200, statusText: "OK", headers: {}, config };
+  };
+  const client = HttpClient.withAxios(axios.create({ adapter }), { baseUrl: "https://api.example.com/v1" }, spec);
+  return { client, urls };
+}
+
+// The operation as the proxy passes it, with the path as listed in the tool lookup
+function operation(specPath: string, path: string = specPath) {
+  return { ...spec.paths[specPath]!.get!, method: "get", path } as OpenAPIV3.OperationObject & {
+    method: string;
+    path: string;
+  };
+}
+
+describe("HttpClient request URLs", () => {
+  it("percent-encodes path parameter values exactly once", async () => {
+    const { client, urls } = recordingClient();
+
+    await client.executeOperation(operation("/spaces/{spaceId}"), { spaceId: "my space/1" });
+    await client.executeOperation(operation("/spaces/{spaceId}"), { spaceId: "space-1" });
+
+    expect(urls).toEqual([
+      "https://api.example.com/v1/spaces/my%20space%2F1",
+      "https://api.example.com/v1/spaces/space-1",
+    ]);
+  });
+
+  it("joins a spec path without a leading slash onto the base URL with one", async () => {
+    const { client, urls } = recordingClient();
+    const listMembers = operation("spaces/{spaceId}/members", "/spaces/{spaceId}/members");
+
+    await client.executeOperation(listMembers, { spaceId: "space-1" });
+
+    expect(urls).toEqual(["https://api.example.com/v1/spaces/space-1/members"]);
+  });
+});
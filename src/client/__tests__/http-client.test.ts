@@ -1,8 +1,16 @@
+import axios from "axios";
 import { Headers } from "node-fetch";
+import fs from "node:fs";
+import http from "node:http";
+import type { AddressInfo } from "node:net";
+import os from "node:os";
+import path from "node:path";
+import { gunzipSync } from "node:zlib";
 import OpenAPIClientAxios from "openapi-client-axios";
 import { OpenAPIV3 } from "openapi-types";
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
-import { HttpClient } from "../http-client";
+import { ClientCredentialsTokenSource } from "../../auth/oauth";
+import { CircuitOpenError, getDefaultHeaders, HttpClient } from "../http-client";
 
 // Mock the OpenAPIClientAxios initialization
 vi.mock("openapi-client-axios", () => {
@@ -10,6 +18,7 @@ vi.mock("openapi-client-axios", () => {
     getPet: vi.fn(),
     testOperation: vi.fn(),
     complexOperation: vi.fn(),
+    head: vi.fn(),
   };
   return {
     default: vi.fn().mockImplementation(() => ({
@@ -69,6 +78,7 @@ describe("HttpClient", () => {
 
   afterEach(() => {
     vi.clearAllMocks();
+    vi.unstubAllEnvs();
   });
 
   it("successfully executes an operation", async () => {
@@ -92,6 +102,898 @@ describe("HttpClient", () => {
     expect(response.headers.get("content-type")).toBe("application/json");
   });
 
+  it("logs the response body at trace level", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+    try {
+      mockApi.getPet.mockResolvedValueOnce({
+        data: { id: 1, name: "Fluffy", token: "secret-token" },
+        status: 200,
+        headers: {},
+      });
+
+      await client.executeOperation(getPetOperation, { petId: 1 });
+
+      expect(consoleSpy).toHaveBeenCalledWith(
+        "response body for getPet:",
+        JSON.stringify({ id: 1, name: "Fluffy", token: "[redacted]" }),
+      );
+    } finally {
+      consoleSpy.mockRestore();
+    }
+  });
+
+  it("does not log bodies below trace level", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+    mockApi.getPet.mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} });
+    await client.executeOperation(getPetOperation, { petId: 1 });
+
+    expect(consoleSpy).not.toHaveBeenCalledWith("response body for getPet:", expect.anything());
+    consoleSpy.mockRestore();
+  });
+
+  it("percent-encodes path parameter values", async () => {
+    mockApi.testOperation.mockResolvedValueOnce({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/spaces/{spaceId}",
+      parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { spaceId: "my space/1" });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({ spaceId: "my space/1" }, undefined, {
+      headers: { "Content-Type": null },
+      url: "/spaces/my%20space%2F1",
+    });
+  });
+
+  it("sends basic auth credentials in place of a configured Authorization header", () => {
+    new HttpClient(
+      {
+        baseUrl: "https://api.example.com",
+        headers: { Authorization: "Bearer token" },
+        basicAuth: { username: "user", password: "pass" },
+      },
+      sampleSpec,
+    );
+
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    expect(axiosConfigDefaults.headers.Authorization).toBe(`Basic ${Buffer.from("user:pass").toString("base64")}`);
+  });
+
+  it("uses an HTTP/2 adapter only when prior knowledge is enabled", () => {
+    new HttpClient({ baseUrl: "http://localhost:31009", http2PriorKnowledge: true }, sampleSpec);
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    expect(axiosConfigDefaults.adapter).toEqual(expect.any(Function));
+
+    new HttpClient({ baseUrl: "http://localhost:31009" }, sampleSpec);
+    const [{ axiosConfigDefaults: defaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    expect(defaults).not.toHaveProperty("adapter");
+  });
+
+  it("sends requests through an injected axios instance with the default headers applied", async () => {
+    const instance = { request: vi.fn().mockResolvedValue({ data: { id: 1 }, status: 200, headers: {} }) };
+    const config = { baseUrl: "https://api.example.com", apiKey: "k" };
+    const injected = HttpClient.withAxios(instance as any, config, sampleSpec);
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+
+    mockApi.getPet.mockImplementationOnce((_params: unknown, data: unknown, config: any) =>
+      axiosConfigDefaults.adapter({
+        ...config,
+        method: "get",
+        url: "/pets/1",
+        data,
+        headers: { ...axiosConfigDefaults.headers, ...config.headers },
+      }),
+    );
+    const response = await injected.executeOperation(getPetOperation, { petId: 1 });
+
+    expect(instance.request).toHaveBeenCalledWith(
+      expect.objectContaining({ url: "/pets/1", headers: expect.objectContaining({ Authorization: "Bearer k" }) }),
+    );
+    expect(response.data).toEqual({ id: 1 });
+  });
+
+  it("returns a redirect as it is when following redirects is disabled", async () => {
+    const server = http.createServer((_req, res) => {
+      res.writeHead(302, { Location: "/elsewhere" }).end();
+    });
+    await new Promise<void>((resolve) => server.listen(0, "127.0.0.1", resolve));
+    const { port } = server.address() as AddressInfo;
+    new HttpClient({ baseUrl: `http://127.0.0.1:${port}`, maxRedirects: 0 }, sampleSpec);
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+
+    try {
+      const response = await axios.request({ ...axiosConfigDefaults, url: "/pets/1" });
+
+      expect(response.status).toBe(302);
+      expect(response.headers.location).toBe("/elsewhere");
+    } finally {
+      server.close();
+    }
+  });
+
+  it("fails tool calls in offline mode without contacting the API", async () => {
+    const offlineClient = new HttpClient({ baseUrl: "https://api.example.com", offline: true }, sampleSpec);
+
+    await expect(offlineClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow(
+      "Offline mode is on, not calling the API for getPet",
+    );
+    await offlineClient.warmup();
+
+    expect(mockApi.getPet).not.toHaveBeenCalled();
+    expect(mockApi.head).not.toHaveBeenCalled();
+  });
+
+  it("short-circuits an operation after repeated server errors until the cooldown has passed", async () => {
+    vi.useFakeTimers();
+    const breakerClient = new HttpClient(
+      { baseUrl: "https://api.example.com", circuitBreakerThreshold: 2, circuitBreakerCooldownMs: 1000 },
+      sampleSpec,
+    );
+    const serverError = { response: { status: 500, statusText: "Internal Server Error", data: {}, headers: {} } };
+    mockApi.getPet.mockRejectedValue(serverError);
+
+    await expect(breakerClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("500");
+    await expect(breakerClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("500");
+    await expect(breakerClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow(CircuitOpenError);
+    expect(mockApi.getPet).toHaveBeenCalledTimes(2);
+
+    // Half-open after the cooldown: a successful trial call closes the circuit again
+    vi.advanceTimersByTime(1000);
+    mockApi.getPet.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    await breakerClient.executeOperation(getPetOperation, { petId: 1 });
+    await breakerClient.executeOperation(getPetOperation, { petId: 1 });
+    expect(mockApi.getPet).toHaveBeenCalledTimes(4);
+    vi.useRealTimers();
+  });
+
+  it("lets a single trial call through while the circuit is half-open", async () => {
+    vi.useFakeTimers();
+    const breakerClient = new HttpClient(
+      { baseUrl: "https://api.example.com", circuitBreakerThreshold: 1, circuitBreakerCooldownMs: 1000 },
+      sampleSpec,
+    );
+    mockApi.getPet.mockRejectedValueOnce({
+      response: { status: 500, statusText: "Internal Server Error", data: {}, headers: {} },
+    });
+    await expect(breakerClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("500");
+    vi.advanceTimersByTime(1000);
+
+    let finishTrial!: (response: unknown) => void;
+    mockApi.getPet.mockReturnValueOnce(new Promise((resolve) => (finishTrial = resolve)));
+    const trial = breakerClient.executeOperation(getPetOperation, { petId: 1 });
+    await expect(breakerClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow(CircuitOpenError);
+
+    finishTrial({ data: { id: 1 }, status: 200, headers: {} });
+    await expect(trial).resolves.toMatchObject({ data: { id: 1 } });
+    mockApi.getPet.mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} });
+    await breakerClient.executeOperation(getPetOperation, { petId: 1 });
+    expect(mockApi.getPet).toHaveBeenCalledTimes(3);
+    vi.useRealTimers();
+  });
+
+  it("decodes JSON responses with the charset declared in their content type", () => {
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    const [decode] = axiosConfigDefaults.transformResponse;
+    const body = Buffer.from(JSON.stringify({ name: "Zoë", tags: ["ünïcode"] }), "utf16le");
+
+    expect(axiosConfigDefaults.responseType).toBe("arraybuffer");
+    expect(decode(body, { "content-type": "application/json; charset=utf-16le" })).toEqual({
+      name: "Zoë",
+      tags: ["ünïcode"],
+    });
+    expect(decode(Buffer.from('{"ok":true}'), { "Content-Type": "application/json" })).toEqual({ ok: true });
+    expect(decode(Buffer.from("plain text"), { "content-type": "text/plain" })).toBe("plain text");
+  });
+
+  it("keeps binary response bodies as bytes", () => {
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    const [decode] = axiosConfigDefaults.transformResponse;
+    const png = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x00, 0xff]);
+    const arrayBuffer = png.buffer.slice(png.byteOffset, png.byteOffset + png.length);
+
+    expect(decode(png, { "content-type": "image/png" })).toEqual(png);
+    expect(decode(arrayBuffer, { "Content-Type": "application/pdf" })).toEqual(png);
+  });
+
+  it("parses JSON bodies served with a non-JSON content type", () => {
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    const [decode] = axiosConfigDefaults.transformResponse;
+    const textPlain = { "content-type": "text/plain; charset=utf-8" };
+
+    expect(decode(Buffer.from(' {"id":"obj1","tags":[]}'), textPlain)).toEqual({ id: "obj1", tags: [] });
+    expect(decode(Buffer.from("[1,2]"), textPlain)).toEqual([1, 2]);
+    expect(decode(Buffer.from("{not json"), textPlain)).toBe("{not json");
+    expect(decode(Buffer.from("42"), textPlain)).toBe("42");
+  });
+
+  it("builds the client with the configured minimum TLS version", () => {
+    new HttpClient({ baseUrl: "https://api.example.com", minTlsVersion: "1.3" }, sampleSpec);
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+
+    expect(axiosConfigDefaults.httpsAgent.options.minVersion).toBe("TLSv1.3");
+  });
+
+  it("sends headers configured as case-sensitive with their exact casing", async () => {
+    const caseClient = new HttpClient(
+      {
+        baseUrl: "https://api.example.com",
+        headers: { "x-anytype-client": "mcp" },
+        caseSensitiveHeaders: ["X-AnyType-Client", "range"],
+      },
+      sampleSpec,
+    );
+    const [{ axiosConfigDefaults }] = vi.mocked(OpenAPIClientAxios).mock.lastCall as any[];
+    mockApi.getPet.mockResolvedValueOnce({ data: {}, status: 206, headers: {} });
+
+    await caseClient.executeOperation(getPetOperation, { petId: 1, _range: "bytes=0-9" });
+
+    expect(Object.keys(axiosConfigDefaults.headers)).toContain("X-AnyType-Client");
+    expect(Object.keys(axiosConfigDefaults.headers)).not.toContain("x-anytype-client");
+    expect(mockApi.getPet).toHaveBeenCalledWith({ petId: 1 }, undefined, {
+      headers: { "Content-Type": null, range: "bytes=0-9" },
+    });
+  });
+
+  it("serves the cached body when a GET is revalidated with 304", async () => {
+    const cachingClient = new HttpClient({ baseUrl: "https://api.example.com", etagCache: true }, sampleSpec);
+    const operation = { ...getPetOperation, method: "get", path: "/pets/{petId}" };
+    mockApi.getPet
+      .mockResolvedValueOnce({ data: { id: 1, name: "Fluffy" }, status: 200, headers: { etag: '"v1"' } })
+      .mockResolvedValueOnce({ data: "", status: 304, headers: { etag: '"v1"' } });
+
+    await cachingClient.executeOperation(operation, { petId: 1 });
+    const response = await cachingClient.executeOperation(operation, { petId: 1 });
+
+    expect(mockApi.getPet).toHaveBeenLastCalledWith({ petId: 1 }, undefined, {
+      headers: { "Content-Type": null, "If-None-Match": '"v1"' },
+      validateStatus: expect.any(Function),
+    });
+    expect(response.data).toEqual({ id: 1, name: "Fluffy" });
+  });
+
+  it("treats status codes the operation declares as successes", async () => {
+    const operation = {
+      ...getPetOperation,
+      responses: { ...getPetOperation.responses, "302": { description: "Found, the export is elsewhere" } },
+    };
+    mockApi.getPet.mockResolvedValueOnce({ data: "", status: 302, headers: { location: "/exports/1" } });
+
+    const response = await client.executeOperation(operation, { petId: 1 });
+
+    const [, , requestConfig] = mockApi.getPet.mock.lastCall;
+    expect(requestConfig.validateStatus(302)).toBe(true);
+    expect(requestConfig.validateStatus(301)).toBe(false);
+    expect(requestConfig.validateStatus(404)).toBe(false);
+    expect(response.status).toBe(302);
+    expect(response.headers.get("location")).toBe("/exports/1");
+  });
+
+  it("fetches a fresh response after the cache was cleared", async () => {
+    const cachingClient = new HttpClient({ baseUrl: "https://api.example.com", etagCache: true }, sampleSpec);
+    const operation = { ...getPetOperation, method: "get", path: "/pets/{petId}" };
+    mockApi.getPet
+      .mockResolvedValueOnce({ data: { id: 1, name: "Fluffy" }, status: 200, headers: { etag: '"v1"' } })
+      .mockResolvedValueOnce({ data: { id: 1, name: "Rex" }, status: 200, headers: { etag: '"v2"' } });
+
+    await cachingClient.executeOperation(operation, { petId: 1 });
+    cachingClient.clearCache();
+    const response = await cachingClient.executeOperation(operation, { petId: 1 });
+
+    expect(mockApi.getPet).toHaveBeenLastCalledWith({ petId: 1 }, undefined, { headers: { "Content-Type": null } });
+    expect(response.data).toEqual({ id: 1, name: "Rex" });
+  });
+
+  it("sends binary request bodies as raw bytes", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "put",
+      path: "/files/{fileId}",
+      parameters: [{ name: "fileId", in: "path", required: true, schema: { type: "string" } }],
+      requestBody: { content: { "image/png": { schema: { type: "string", format: "binary" } } } },
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+    const bytes = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x00, 0xff]);
+
+    await client.executeOperation(operation, { fileId: "f1", body: bytes.toString("base64") });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({ fileId: "f1" }, bytes, {
+      headers: { "Content-Type": "image/png" },
+    });
+  });
+
+  it("follows the cursor to fetch every page of a paginated operation", async () => {
+    mockApi.testOperation
+      .mockResolvedValueOnce({ data: { data: [{ id: 1 }, { id: 2 }], next_cursor: "c2" }, status: 200, headers: {} })
+      .mockResolvedValueOnce({ data: { data: [{ id: 3 }], next_cursor: null }, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/objects",
+      parameters: [
+        { name: "limit", in: "query", schema: { type: "integer" } },
+        { name: "cursor", in: "query", schema: { type: "string" } },
+      ],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    const response = await client.executeOperationAll(operation, { limit: 2 });
+
+    expect(mockApi.testOperation).toHaveBeenCalledTimes(2);
+    expect(mockApi.testOperation).toHaveBeenLastCalledWith({ limit: 2, cursor: "c2" }, undefined, expect.anything());
+    expect(response.data).toEqual({ data: [{ id: 1 }, { id: 2 }, { id: 3 }], next_cursor: null });
+  });
+
+  it("stops following pages at the page cap", async () => {
+    mockApi.testOperation.mockResolvedValue({
+      data: { data: [{ id: 1 }], next_cursor: "more" },
+      status: 200,
+      headers: {},
+    });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/objects",
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    const response = await client.executeOperationAll(operation, {}, { maxPages: 3 });
+
+    expect(mockApi.testOperation).toHaveBeenCalledTimes(3);
+    expect(response.data.data).toHaveLength(3);
+  });
+
+  it("retries a request rejected with 401 once with the new key from the key file", async () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+    const apiKeyFile = path.join(dir, "api-key");
+    fs.writeFileSync(apiKeyFile, "new-key\n");
+    const unauthorized = { response: { status: 401, statusText: "Unauthorized", headers: {}, data: {} } };
+    mockApi.getPet
+      .mockRejectedValueOnce(unauthorized)
+      .mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} })
+      .mockRejectedValueOnce(unauthorized);
+
+    try {
+      const reauthClient = new HttpClient(
+        { baseUrl: "https://api.example.com", apiKey: "old-key", apiKeyFile },
+        sampleSpec,
+      );
+      const response = await reauthClient.executeOperation(getPetOperation, { petId: 1 });
+
+      expect(response.data).toEqual({ id: 1 });
+      expect(mockApi.getPet).toHaveBeenNthCalledWith(2, { petId: 1 }, undefined, {
+        headers: { "Content-Type": null, Authorization: "Bearer new-key" },
+      });
+
+      // The key in the file is the one already rejected, so there is nothing to retry with
+      await expect(reauthClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("401 Unauthorized");
+      expect(mockApi.getPet).toHaveBeenCalledTimes(3);
+    } finally {
+      fs.rmSync(dir, { recursive: true });
+    }
+  });
+
+  it("sends the OAuth token instead of the API key", async () => {
+    const getToken = vi.spyOn(ClientCredentialsTokenSource.prototype, "getToken").mockResolvedValue("oauth-token");
+    mockApi.getPet.mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} });
+    const oauthClient = new HttpClient(
+      {
+        baseUrl: "https://api.example.com",
+        apiKey: "static-key",
+        oauth: { tokenUrl: "https://auth.example.com/token", clientId: "id", clientSecret: "secret" },
+      },
+      sampleSpec,
+    );
+
+    await oauthClient.executeOperation(getPetOperation, { petId: 1 });
+
+    expect(getToken).toHaveBeenCalledTimes(1);
+    expect(mockApi.getPet).toHaveBeenCalledWith({ petId: 1 }, undefined, {
+      headers: { "Content-Type": null, Authorization: "Bearer oauth-token" },
+    });
+    getToken.mockRestore();
+  });
+
+  it("never logs the OAuth token", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    const getToken = vi.spyOn(ClientCredentialsTokenSource.prototype, "getToken").mockResolvedValue("oauth-token");
+    mockApi.getPet.mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} });
+
+    try {
+      const oauthClient = new HttpClient(
+        {
+          baseUrl: "https://api.example.com",
+          oauth: { tokenUrl: "https://auth.example.com/token", clientId: "id", clientSecret: "secret" },
+        },
+        sampleSpec,
+      );
+      await oauthClient.executeOperation(getPetOperation, { petId: 1 });
+
+      expect(consoleSpy).toHaveBeenCalled();
+      for (const args of consoleSpy.mock.calls) {
+        expect(JSON.stringify(args)).not.toContain("oauth-token");
+      }
+    } finally {
+      consoleSpy.mockRestore();
+      getToken.mockRestore();
+    }
+  });
+
+  it("never logs the OAuth token when the request fails", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    const getToken = vi.spyOn(ClientCredentialsTokenSource.prototype, "getToken").mockResolvedValue("oauth-token");
+    // Like an AxiosError, the rejection carries the config the request was sent with
+    mockApi.getPet.mockImplementationOnce((_params: unknown, _body: unknown, config: unknown) =>
+      Promise.reject({ config, response: { status: 500, statusText: "Server Error", headers: {}, data: {} } }),
+    );
+
+    try {
+      const oauthClient = new HttpClient(
+        {
+          baseUrl: "https://api.example.com",
+          oauth: { tokenUrl: "https://auth.example.com/token", clientId: "id", clientSecret: "secret" },
+        },
+        sampleSpec,
+      );
+      await expect(oauthClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("Server Error");
+
+      expect(consoleSpy).toHaveBeenCalledWith("Error in http client: getPet failed with 500", "Server Error");
+      expect(JSON.stringify(consoleSpy.mock.calls)).not.toContain("oauth-token");
+    } finally {
+      consoleSpy.mockRestore();
+      getToken.mockRestore();
+    }
+  });
+
+  it("never logs the refreshed API key", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+    const apiKeyFile = path.join(dir, "api-key");
+    fs.writeFileSync(apiKeyFile, "rotated-key");
+    mockApi.getPet
+      .mockRejectedValueOnce({ response: { status: 401, statusText: "Unauthorized", headers: {}, data: {} } })
+      .mockResolvedValueOnce({ data: { id: 1 }, status: 200, headers: {} });
+
+    try {
+      const reauthClient = new HttpClient({ baseUrl: "https://api.example.com", apiKeyFile }, sampleSpec);
+      await reauthClient.executeOperation(getPetOperation, { petId: 1 });
+
+      expect(consoleSpy).toHaveBeenCalledWith(
+        "request headers for getPet:",
+        JSON.stringify({ "Content-Type": null, Authorization: "[redacted]" }),
+      );
+      expect(JSON.stringify(consoleSpy.mock.calls)).not.toContain("rotated-key");
+    } finally {
+      consoleSpy.mockRestore();
+      fs.rmSync(dir, { recursive: true });
+    }
+  });
+
+  it("never logs the refreshed API key when the retried request fails too", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-"));
+    const apiKeyFile = path.join(dir, "api-key");
+    fs.writeFileSync(apiKeyFile, "rotated-key");
+    // Like an AxiosError, each rejection carries the config the request was sent with
+    const reject = (_params: unknown, _body: unknown, config: unknown) =>
+      Promise.reject({ config, response: { status: 401, statusText: "Unauthorized", headers: {}, data: {} } });
+    mockApi.getPet.mockImplementationOnce(reject).mockImplementationOnce(reject);
+
+    try {
+      const reauthClient = new HttpClient({ baseUrl: "https://api.example.com", apiKeyFile }, sampleSpec);
+      await expect(reauthClient.executeOperation(getPetOperation, { petId: 1 })).rejects.toThrow("401 Unauthorized");
+
+      expect(mockApi.getPet).toHaveBeenCalledTimes(2);
+      expect(consoleSpy).toHaveBeenCalledWith("Error in http client: getPet failed with 401", "Unauthorized");
+      expect(JSON.stringify(consoleSpy.mock.calls)).not.toContain("rotated-key");
+    } finally {
+      consoleSpy.mockRestore();
+      fs.rmSync(dir, { recursive: true });
+    }
+  });
+
+  it("lets the API key override a configured Authorization header and warns about it", () => {
+    const warnSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+
+    const headers = getDefaultHeaders({
+      baseUrl: "https://api.example.com",
+      headers: { authorization: "Bearer from-headers", "X-Custom": "1" },
+      apiKey: "from-api-key",
+    });
+
+    expect(headers).toEqual({
+      "Content-Type": "application/json",
+      "User-Agent": "anytype-mcp-server",
+      "X-Custom": "1",
+      Authorization: "Bearer from-api-key",
+    });
+    expect(warnSpy).toHaveBeenCalledWith("Configured Authorization header is overridden by the API key");
+    warnSpy.mockRestore();
+  });
+
+  it("leaves out disabled default headers whatever their casing", () => {
+    const headers = getDefaultHeaders({
+      headers: { "Anytype-Version": "2025-05-20", "X-Custom": "1" },
+      disableDefaultHeaders: ["anytype-version"],
+    });
+
+    expect(headers).toEqual({
+      "Content-Type": "application/json",
+      "User-Agent": "anytype-mcp-server",
+      "X-Custom": "1",
+    });
+  });
+
+  it("sends the configured Accept-Language header only when it is set", () => {
+    expect(getDefaultHeaders({ acceptLanguage: "de-DE" })["Accept-Language"]).toBe("de-DE");
+    expect(getDefaultHeaders({})).not.toHaveProperty("Accept-Language");
+  });
+
+  it("sends the same body whether it is wrapped in a body argument or passed flat", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "post",
+      path: "/objects",
+      requestBody: {
+        content: {
+          "application/json": {
+            schema: { allOf: [{ type: "object", properties: { name: { type: "string" } } }] },
+          },
+        },
+      },
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { body: { name: "Fluffy" } });
+    await client.executeOperation(operation, { name: "Fluffy" });
+
+    const expectedConfig = { headers: { "Content-Type": "application/json" } };
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(1, {}, { name: "Fluffy" }, expectedConfig);
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, {}, { name: "Fluffy" }, expectedConfig);
+  });
+
+  it("sends a JSON body for a DELETE operation that declares one", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const bulkDelete = {
+      operationId: "testOperation",
+      method: "delete",
+      path: "/spaces/{spaceId}/objects",
+      parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+      requestBody: {
+        content: {
+          "application/json": {
+            schema: { type: "object", properties: { object_ids: { type: "array", items: { type: "string" } } } },
+          },
+        },
+      },
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+    const queryDelete = { ...bulkDelete, requestBody: undefined };
+    const ids = { object_ids: ["a", "b"] };
+
+    await client.executeOperation(bulkDelete, { spaceId: "s1", ...ids });
+    await client.executeOperation(queryDelete, { spaceId: "s1", ...ids });
+
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(1, { spaceId: "s1" }, ids, {
+      headers: { "Content-Type": "application/json" },
+    });
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, { spaceId: "s1", ...ids }, undefined, {
+      headers: { "Content-Type": null },
+    });
+  });
+
+  it("logs rather than throws when the warmup request fails", async () => {
+    const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    mockApi.head.mockRejectedValueOnce(new Error("connect ECONNREFUSED 127.0.0.1:31009"));
+
+    await expect(client.warmup()).resolves.toBeUndefined();
+
+    expect(mockApi.head).toHaveBeenCalledWith("/health", { validateStatus: expect.any(Function) });
+    expect(errorSpy).toHaveBeenCalledWith("Connection warmup failed:", "connect ECONNREFUSED 127.0.0.1:31009");
+    errorSpy.mockRestore();
+  });
+
+  it("sends cookie parameters in a Cookie header instead of the query string", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/session",
+      parameters: [
+        { name: "session_id", in: "cookie", required: true, schema: { type: "string" } },
+        { name: "verbose", in: "query", schema: { type: "boolean" } },
+      ],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { session_id: "abc 123", verbose: true });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({ verbose: true }, undefined, {
+      headers: { "Content-Type": null, Cookie: "session_id=abc%20123" },
+    });
+  });
+
+  it("redacts the Cookie header in the request log", async () => {
+    vi.stubEnv("OPENAPI_MCP_LOG_LEVEL", "trace");
+    const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/session",
+      parameters: [{ name: "session_id", in: "cookie", required: true, schema: { type: "string" } }],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    try {
+      await client.executeOperation(operation, { session_id: "abc123" });
+
+      expect(consoleSpy).toHaveBeenCalledWith(
+        "request headers for testOperation:",
+        JSON.stringify({ "Content-Type": null, Cookie: "[redacted]" }),
+      );
+      expect(JSON.stringify(consoleSpy.mock.calls)).not.toContain("abc123");
+    } finally {
+      consoleSpy.mockRestore();
+    }
+  });
+
+  it("forwards the _range argument as a Range header and returns the partial content", async () => {
+    mockApi.getPet.mockResolvedValueOnce({
+      data: "first hundred bytes",
+      status: 206,
+      headers: { "content-range": "bytes 0-99/1000" },
+    });
+
+    const response = await client.executeOperation(getPetOperation, { petId: 1, _range: "bytes=0-99" });
+
+    expect(mockApi.getPet).toHaveBeenCalledWith({ petId: 1 }, undefined, {
+      headers: { "Content-Type": null, Range: "bytes=0-99" },
+    });
+    expect(response.status).toBe(206);
+    expect(response.data).toBe("first hundred bytes");
+    expect(response.headers.get("content-range")).toBe("bytes 0-99/1000");
+  });
+
+  it("forwards the _prefer argument as a Prefer header instead of sending it in the body", async () => {
+    const preferClient = new HttpClient({ baseUrl: "https://api.example.com", preferRepresentation: true }, sampleSpec);
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 201, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "post",
+      path: "/objects",
+      requestBody: {
+        content: { "application/json": { schema: { type: "object", properties: { name: { type: "string" } } } } },
+      },
+      responses: { "201": { description: "Created" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await preferClient.executeOperation(operation, { name: "Note", _prefer: "return=minimal" });
+    await preferClient.executeOperation(operation, { name: "Note" });
+
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(1, {}, { name: "Note" }, {
+      headers: { "Content-Type": "application/json", Prefer: "return=minimal" },
+    });
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, {}, { name: "Note" }, {
+      headers: { "Content-Type": "application/json", Prefer: "return=representation" },
+    });
+  });
+
+  describe("request compression", () => {
+    const operation = {
+      operationId: "testOperation",
+      method: "post",
+      path: "/objects",
+      requestBody: {
+        content: { "application/json": { schema: { type: "object", properties: { markdown: { type: "string" } } } } },
+      },
+      responses: { "201": { description: "Created" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+    const largeBody = { markdown: "x".repeat(2048) };
+
+    it("gzips large JSON bodies when enabled", async () => {
+      const compressClient = new HttpClient({ baseUrl: "https://api.example.com", compressRequest: true }, sampleSpec);
+      mockApi.testOperation.mockResolvedValue({ data: {}, status: 201, headers: {} });
+
+      await compressClient.executeOperation(operation, largeBody);
+      await compressClient.executeOperation(operation, { markdown: "short" });
+
+      const [, sent, requestConfig] = mockApi.testOperation.mock.calls[0];
+      expect(requestConfig.headers["Content-Encoding"]).toBe("gzip");
+      expect(JSON.parse(gunzipSync(sent).toString())).toEqual(largeBody);
+      expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, {}, { markdown: "short" }, {
+        headers: { "Content-Type": "application/json" },
+      });
+    });
+
+    it("sends large bodies uncompressed when disabled", async () => {
+      mockApi.testOperation.mockResolvedValue({ data: {}, status: 201, headers: {} });
+
+      await client.executeOperation(operation, largeBody);
+
+      expect(mockApi.testOperation).toHaveBeenCalledWith({}, largeBody, {
+        headers: { "Content-Type": "application/json" },
+      });
+    });
+
+    it("stops compressing once the server rejects a compressed body", async () => {
+      const compressClient = new HttpClient({ baseUrl: "https://api.example.com", compressRequest: true }, sampleSpec);
+      mockApi.testOperation
+        .mockRejectedValueOnce({ response: { status: 415, statusText: "Unsupported Media Type", headers: {} } })
+        .mockResolvedValue({ data: {}, status: 201, headers: {} });
+
+      const response = await compressClient.executeOperation(operation, largeBody);
+      await compressClient.executeOperation(operation, largeBody);
+
+      expect(response.status).toBe(201);
+      expect(mockApi.testOperation).toHaveBeenCalledTimes(3);
+      expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, {}, largeBody, {
+        headers: { "Content-Type": "application/json" },
+      });
+      expect(mockApi.testOperation).toHaveBeenNthCalledWith(3, {}, largeBody, {
+        headers: { "Content-Type": "application/json" },
+      });
+    });
+  });
+
+  it("sends both query parameters and a JSON body for a GET operation declaring a body", async () => {
+    const cachingClient = new HttpClient({ baseUrl: "https://api.example.com", etagCache: true }, sampleSpec);
+    mockApi.testOperation.mockResolvedValue({ data: [], status: 200, headers: { etag: '"v1"' } });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/search",
+      parameters: [{ name: "limit", in: "query", schema: { type: "integer" } }],
+      requestBody: {
+        content: { "application/json": { schema: { type: "object", properties: { query: { type: "string" } } } } },
+      },
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await cachingClient.executeOperation(operation, { limit: 10, query: "notes" });
+    await cachingClient.executeOperation(operation, { limit: 10, query: "tasks" });
+
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(1, { limit: 10 }, { query: "notes" }, {
+      headers: { "Content-Type": "application/json" },
+    });
+    // A different body is a different search, so it is not revalidated against the first one
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(2, { limit: 10 }, { query: "tasks" }, {
+      headers: { "Content-Type": "application/json" },
+    });
+  });
+
+  it("sends extra keys for a body open to additional properties", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 201, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "post",
+      path: "/objects",
+      requestBody: {
+        content: {
+          "application/json": {
+            schema: { type: "object", properties: { name: { type: "string" } }, additionalProperties: true },
+          },
+        },
+      },
+      responses: { "201": { description: "Created" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { name: "Note", mood: "calm", rating: 5 });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({}, { name: "Note", mood: "calm", rating: 5 }, {
+      headers: { "Content-Type": "application/json" },
+    });
+  });
+
+  it("fills in omitted parameters that are pinned with const", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/spaces",
+      parameters: [{ name: "version", in: "query", schema: { type: "string", const: "v1" } }],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, {});
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({ version: "v1" }, undefined, {
+      headers: { "Content-Type": null },
+    });
+  });
+
+  it("sends omitted query parameters with their declared default", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/spaces",
+      parameters: [
+        { name: "limit", in: "query", schema: { type: "integer", default: 100 } },
+        { name: "offset", in: "query", schema: { type: "integer", default: 0 } },
+      ],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { offset: 20 });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith({ limit: 100, offset: 20 }, undefined, {
+      headers: { "Content-Type": null },
+    });
+  });
+
+  it("uses the method and path of an overridden operation instead of the declared ones", async () => {
+    mockApi.getPet.mockResolvedValueOnce({ data: {}, status: 200, headers: {} });
+    const overridden = { ...getPetOperation, method: "post", path: "/pets/{petId}/lookup" };
+
+    await client.executeOperation(overridden, { petId: 1 });
+
+    expect(mockApi.getPet).toHaveBeenCalledWith({ petId: 1 }, undefined, {
+      headers: { "Content-Type": null },
+      method: "post",
+      url: "/pets/1/lookup",
+    });
+  });
+
+  it("serializes object-valued query parameters per their declared style", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = (parameter: Partial<OpenAPIV3.ParameterObject>) =>
+      ({
+        operationId: "testOperation",
+        method: "get",
+        path: "/objects",
+        parameters: [{ name: "filter", in: "query", schema: { type: "object" }, ...parameter }],
+        responses: { "200": { description: "OK" } },
+      }) as OpenAPIV3.OperationObject & { method: string; path: string };
+    const filter = { status: "active", type: "page" };
+
+    await client.executeOperation(operation({}), { filter });
+    await client.executeOperation(operation({ explode: false }), { filter });
+
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(1, filter, undefined, expect.anything());
+    expect(mockApi.testOperation).toHaveBeenNthCalledWith(
+      2,
+      { filter: "status,active,type,page" },
+      undefined,
+      expect.anything(),
+    );
+    await expect(client.executeOperation(operation({ style: "pipeDelimited" }), { filter })).rejects.toThrow(
+      'Query parameter filter is an object, which style "pipeDelimited" cannot serialize',
+    );
+  });
+
+  it("serializes deepObject query parameters into bracketed keys", async () => {
+    mockApi.testOperation.mockResolvedValue({ data: {}, status: 200, headers: {} });
+    const operation = {
+      operationId: "testOperation",
+      method: "get",
+      path: "/objects",
+      parameters: [{ name: "filter", in: "query", style: "deepObject", explode: true, schema: { type: "object" } }],
+      responses: { "200": { description: "OK" } },
+    } as OpenAPIV3.OperationObject & { method: string; path: string };
+
+    await client.executeOperation(operation, { filter: { status: "active", created: { gte: "2024-01-01" } } });
+
+    expect(mockApi.testOperation).toHaveBeenCalledWith(
+      { "filter[status]": "active", "filter[created][gte]": "2024-01-01" },
+      undefined,
+      expect.anything(),
+    );
+  });
+
   it("throws error when operation ID is missing", async () => {
     const operationWithoutId: OpenAPIV3.OperationObject & { method: string; path: string } = {
       method: "GET",
@@ -1,16 +1,115 @@
-import type { AxiosInstance } from "axios";
+import type { AxiosAdapter, AxiosInstance } from "axios";
 import FormData from "form-data";
 import fs from "fs";
+import https from "https";
 import { Headers } from "node-fetch";
+import { gzipSync } from "node:zlib";
 import OpenAPIClientAxios from "openapi-client-axios";
 import type { OpenAPIV3, OpenAPIV3_1 } from "openapi-types";
+import { ClientCredentialsTokenSource } from "../auth/oauth";
+import {
+  DEFAULT_HEALTH_PATH,
+  readApiKeyFile,
+  type BasicAuthCredentials,
+  type OAuthClientCredentials,
+  type TlsVersion,
+} from "../config";
+import { traceBody } from "../logger";
+import { createHttp2Adapter } from "./http2-adapter";
 import { isFileUploadParameter } from "../openapi/file-upload";
 
+/**
+ * Header precedence, lowest to highest: built-in defaults, headers, acceptLanguage, apiKey, basicAuth, oauth.
+ */
 export type HttpClientConfig = {
   baseUrl: string;
   headers?: Record<string, string>;
+  // Sent as Accept-Language, for APIs that localize their responses
+  acceptLanguage?: string;
+  // Sent as a Bearer token
+  apiKey?: string;
+  // Read again for a new API key when the API answers 401, and the request retried once with it
+  apiKeyFile?: string;
+  basicAuth?: BasicAuthCredentials;
+  // Bearer tokens are fetched from the token URL, cached until shortly before they expire, and sent instead of apiKey
+  oauth?: OAuthClientCredentials;
+  // Revalidate repeated GETs with If-None-Match and serve the cached body on 304
+  etagCache?: boolean;
+  // Speak HTTP/2 without negotiating it first, instead of the default HTTP/1.1
+  http2PriorKnowledge?: boolean;
+  // Lowest TLS version accepted when connecting over HTTPS, Node's default otherwise
+  minTlsVersion?: TlsVersion;
+  // Requested by warmup, DEFAULT_HEALTH_PATH otherwise
+  healthPath?: string;
+  // Header names sent with exactly this casing, for servers that care about it
+  caseSensitiveHeaders?: string[];
+  // Header names left out of the headers sent with every request, matched case-insensitively
+  disableDefaultHeaders?: string[];
+  // Redirects followed per request, 0 returns redirect responses as they are. axios follows 21 by default
+  maxRedirects?: number;
+  // Refuse to make any request, e.g. in tests and CI
+  offline?: boolean;
+  // Send Prefer: return=representation on POST requests unless the _prefer argument says otherwise
+  preferRepresentation?: boolean;
+  // Gzip JSON request bodies of at least compressRequestMinBytes and send them with Content-Encoding: gzip
+  compressRequest?: boolean;
+  compressRequestMinBytes?: number;
+  // Consecutive failures after which calls to an operation are short-circuited, never when unset
+  circuitBreakerThreshold?: number;
+  // How long a tripped operation is short-circuited before a trial call is let through
+  circuitBreakerCooldownMs?: number;
 };
 
+// Used when compressRequest is set without a threshold
+export const DEFAULT_COMPRESS_REQUEST_MIN_BYTES = 1024;
+
+// Used when circuitBreakerThreshold is set without a cooldown
+export const DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS = 30_000;
+
+type CachedResponse = {
+  etag: string;
+  data: any;
+};
+
+type CircuitState = {
+  failures: number;
+  openUntil: number;
+  // Set while the single trial call of a half-open circuit is in flight
+  trialInFlight?: boolean;
+};
+
+/**
+ * A file passed inline through the special `_files` argument of a multipart operation
+ */
+export type MultipartFile = {
+  field: string;
+  filename: string;
+  // base64-encoded file content
+  data: string;
+  content_type?: string;
+};
+
+/**
+ * A JSON metadata part passed through the special `_json_part` argument of a multipart operation
+ */
+export type MultipartJsonPart = {
+  field: string;
+  value: unknown;
+};
+
+/**
+ * How executeOperationAll finds the next page: the response field holding the cursor and the
+ * parameter it is passed back in
+ */
+export type PaginationOptions = {
+  cursorField?: string;
+  cursorParam?: string;
+  // Stop after this many pages even if there are more
+  maxPages?: number;
+};
+
+export const DEFAULT_MAX_PAGES = 50;
+
 export type HttpClientResponse<T = any> = {
   data: T;
   status: number;
@@ -29,39 +128,374 @@ export class HttpClientError extends Error {
   }
 }
 
+// Headers the client sets on its own, which configured headers may clash with
+const RESERVED_HEADERS = ["Content-Type", "User-Agent", "Authorization"];
+
+function isPlainObject(value: unknown): value is Record<string, any> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function basicAuthHeader({ username, password }: BasicAuthCredentials): string {
+  return `Basic ${Buffer.from(`${username}:${password}`).toString("base64")}`;
+}
+
+function findHeader(headers: Record<string, string>, name: string): string | undefined {
+  return Object.keys(headers).find((key) => key.toLowerCase() === name.toLowerCase());
+}
+
+function setHeader(headers: Record<string, string>, name: string, value: string) {
+  const existing = findHeader(headers, name);
+  if (existing) {
+    delete headers[existing];
+  }
+  headers[name] = value;
+}
+
+// Flatten an object into name[key]=value pairs, nesting brackets for nested objects
+function deepObjectEntries(name: string, value: Record<string, any>): Record<string, any> {
+  const entries: Record<string, any> = {};
+  for (const [key, nested] of Object.entries(value)) {
+    if (isPlainObject(nested)) {
+      Object.assign(entries, deepObjectEntries(`${name}[${key}]`, nested));
+    } else if (nested !== undefined) {
+      entries[`${name}[${key}]`] = nested;
+    }
+  }
+  return entries;
+}
+
+/**
+ * Serialize an object-valued query parameter according to its declared style, instead of letting
+ * it be stringified. Form style (the default) sends each property as its own query parameter, or
+ * with explode: false a single comma-separated value. deepObject style sends name[key]=value pairs.
+ */
+function serializeQueryObject(param: OpenAPIV3.ParameterObject, value: Record<string, any>): Record<string, any> {
+  const style = param.style ?? "form";
+  if (style === "deepObject") {
+    return deepObjectEntries(param.name, value);
+  }
+  if (style !== "form") {
+    throw new Error(
+      `Query parameter ${param.name} is an object, which style "${style}" cannot serialize. ` +
+        "Pass its properties as separate arguments instead.",
+    );
+  }
+  if (param.explode === false) {
+    return { [param.name]: Object.entries(value).flat().join(",") };
+  }
+  return value;
+}
+
+function preserveHeaderCase(headers: Record<string, any>, names: string[]) {
+  for (const name of names) {
+    const existing = findHeader(headers, name);
+    if (existing && existing !== name) {
+      setHeader(headers, name, headers[existing]);
+    }
+  }
+}
+
+/**
+ * Build the headers sent with every request, following the precedence documented on HttpClientConfig.
+ * A warning is logged whenever a configured header clashes with a reserved one. Headers named in
+ * disableDefaultHeaders are dropped last, wherever they came from.
+ */
+export function getDefaultHeaders(config: Omit<HttpClientConfig, "baseUrl">): Record<string, string> {
+  const headers: Record<string, string> = {
+    "Content-Type": "application/json",
+    "User-Agent": "anytype-mcp-server",
+  };
+
+  for (const [name, value] of Object.entries(config.headers ?? {})) {
+    const reserved = RESERVED_HEADERS.find((header) => header.toLowerCase() === name.toLowerCase());
+    if (reserved === "Content-Type") {
+      console.warn("Configured Content-Type header is replaced per request based on the request body");
+    } else if (reserved === "User-Agent") {
+      console.warn("Configured User-Agent header overrides the default");
+    }
+    setHeader(headers, name, value);
+  }
+
+  if (config.acceptLanguage) {
+    setHeader(headers, "Accept-Language", config.acceptLanguage);
+  }
+
+  const authorization = config.basicAuth
+    ? basicAuthHeader(config.basicAuth)
+    : config.apiKey
+      ? `Bearer ${config.apiKey}`
+      : undefined;
+  if (authorization) {
+    if (findHeader(headers, "Authorization")) {
+      const source = config.basicAuth ? "basic auth credentials" : "the API key";
+      console.warn(`Configured Authorization header is overridden by ${source}`);
+    }
+    setHeader(headers, "Authorization", authorization);
+  }
+
+  for (const name of config.disableDefaultHeaders ?? []) {
+    const existing = findHeader(headers, name);
+    if (existing) {
+      delete headers[existing];
+    }
+  }
+
+  preserveHeaderCase(headers, config.caseSensitiveHeaders ?? []);
+  return headers;
+}
+
+/**
+ * Thrown instead of sending a request while the circuit breaker of an operation is open
+ */
+export class CircuitOpenError extends Error {
+  constructor(
+    public operationId: string,
+    public retryAfterMs: number,
+  ) {
+    super(
+      `Circuit open for ${operationId} after repeated failures, not calling the API. ` +
+        `Retry in ${Math.ceil(retryAfterMs / 1000)}s`,
+    );
+    this.name = "CircuitOpenError";
+  }
+}
+
+/**
+ * Give every path of the spec a leading slash. Hand-written specs sometimes omit it, and openapi-client-axios
+ * builds request URLs from the paths as they are written.
+ */
+function withNormalizedPaths<T extends OpenAPIV3.Document | OpenAPIV3_1.Document>(openApiSpec: T): T {
+  if (!openApiSpec.paths) {
+    return openApiSpec;
+  }
+  const paths = Object.entries(openApiSpec.paths).map(([path, item]) => [`/${path.replace(/^\/+/, "")}`, item]);
+  return { ...openApiSpec, paths: Object.fromEntries(paths) };
+}
+
+// Media types whose bodies are bytes rather than text, which would be mangled by decoding them
+const BINARY_CONTENT_TYPES = ["application/octet-stream", "application/pdf", "application/zip", "application/gzip"];
+
+function isBinaryContentType(contentType: string): boolean {
+  const mimeType = contentType.split(";")[0].trim();
+  return /^(image|audio|video|font)\//.test(mimeType) || BINARY_CONTENT_TYPES.includes(mimeType);
+}
+
+/**
+ * Decode a raw response body with the charset its Content-Type declares, UTF-8 otherwise, then parse it
+ * as JSON. A body with a mislabeled content type, e.g. text/plain, is still parsed when it starts like a
+ * JSON object or array, and returned as text only when that fails. Binary bodies are returned as a Buffer.
+ */
+function decodeResponseBody(data: unknown, headers: Record<string, any> = {}): unknown {
+  if (!Buffer.isBuffer(data) && !(data instanceof ArrayBuffer)) {
+    return data;
+  }
+  const contentTypeHeader = findHeader(headers, "content-type");
+  const contentType = String((contentTypeHeader && headers[contentTypeHeader]) ?? "").toLowerCase();
+  if (isBinaryContentType(contentType)) {
+    return Buffer.isBuffer(data) ? data : Buffer.from(data);
+  }
+  const charset = contentType.match(/charset\s*=\s*"?([^";\s]+)/)?.[1] ?? "utf-8";
+  let decoder: TextDecoder;
+  try {
+    decoder = new TextDecoder(charset);
+  } catch {
+    console.error(`Unsupported response charset "${charset}", decoding as UTF-8`);
+    decoder = new TextDecoder("utf-8");
+  }
+  const text = decoder.decode(data);
+  const declaredJson = /[/+]json\b/.test(contentType);
+  if (!text || (!declaredJson && !/^\s*[[{]/.test(text))) {
+    return text;
+  }
+  try {
+    return JSON.parse(text);
+  } catch (error) {
+    if (declaredJson) {
+      console.error("Failed to parse JSON response:", error instanceof Error ? error.message : error);
+    }
+    return text;
+  }
+}
+
+/**
+ * Adapter handing requests, already carrying the default headers, over to another axios instance.
+ * The body is already serialized at this point so it is passed through untouched.
+ */
+function delegatingAdapter(instance: AxiosInstance): AxiosAdapter {
+  return (config) => instance.request({ ...config, adapter: undefined, transformRequest: [(data) => data] });
+}
+
 export class HttpClient {
   private api: Promise<AxiosInstance>;
   private client: OpenAPIClientAxios;
+  private config: HttpClientConfig;
+  private openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document;
+  private etagCache = new Map<string, CachedResponse>();
+  private circuits = new Map<string, CircuitState>();
+  // An API key read from apiKeyFile after a 401, replacing the configured one
+  private refreshedApiKey?: string;
+  private tokenSource?: ClientCredentialsTokenSource;
+  // Set once the server turns down a compressed body, after which bodies are sent as they are
+  private compressionRejected = false;
 
-  constructor(config: HttpClientConfig, openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document) {
+  constructor(
+    config: HttpClientConfig,
+    openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document,
+    axiosInstance?: AxiosInstance,
+  ) {
+    this.config = config;
+    this.openApiSpec = openApiSpec;
+    const minVersion = config.minTlsVersion ? (`TLSv${config.minTlsVersion}` as const) : undefined;
+    const adapter = axiosInstance
+      ? delegatingAdapter(axiosInstance)
+      : config.http2PriorKnowledge
+        ? createHttp2Adapter(minVersion ? { minVersion } : {})
+        : undefined;
     // @ts-expect-error OpenAPIClientAxios can be imported as default or named export, we handle both cases
     this.client = new (OpenAPIClientAxios.default ?? OpenAPIClientAxios)({
-      definition: openApiSpec,
+      definition: withNormalizedPaths(openApiSpec),
       axiosConfigDefaults: {
         baseURL: config.baseUrl,
-        headers: {
-          "Content-Type": "application/json",
-          "User-Agent": "anytype-mcp-server",
-          ...config.headers,
-        },
+        headers: getDefaultHeaders(config),
+        // Bodies are decoded here rather than by axios, which assumes UTF-8 whatever the declared charset
+        responseType: "arraybuffer",
+        transformResponse: [decodeResponseBody],
+        ...(config.maxRedirects !== undefined ? { maxRedirects: config.maxRedirects } : {}),
+        // Without redirects to follow, a redirect is a response of its own rather than an error
+        ...(config.maxRedirects === 0 ? { validateStatus: (status: number) => status >= 200 && status < 400 } : {}),
+        ...(minVersion ? { httpsAgent: new https.Agent({ minVersion }) } : {}),
+        ...(adapter ? { adapter } : {}),
       },
     });
     this.api = this.client.init();
+    if (config.oauth) {
+      this.tokenSource = new ClientCredentialsTokenSource(config.oauth);
+    }
+  }
+
+  /**
+   * Create a client that sends its requests through a pre-configured axios instance, e.g. one with
+   * tracing or caching interceptors or custom TLS settings. The default headers are still applied.
+   */
+  static withAxios(
+    axiosInstance: AxiosInstance,
+    config: HttpClientConfig,
+    openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document,
+  ): HttpClient {
+    return new HttpClient(config, openApiSpec, axiosInstance);
+  }
+
+  /**
+   * Find the method and path the spec itself declares for an operation
+   */
+  private declaredRoute(operationId: string): { method: string; path: string } | undefined {
+    for (const [path, pathItem] of Object.entries(this.openApiSpec.paths ?? {})) {
+      for (const [method, operation] of Object.entries(pathItem ?? {})) {
+        if ((operation as OpenAPIV3.OperationObject)?.operationId === operationId) {
+          return { method, path };
+        }
+      }
+    }
+    return undefined;
+  }
+
+  private resolveRef(value: any): any {
+    if (typeof value?.$ref !== "string" || !value.$ref.startsWith("#/")) {
+      return value;
+    }
+    return value.$ref.slice(2).split("/").reduce((node: any, part: string) => node?.[part], this.openApiSpec);
+  }
+
+  /**
+   * Whether the JSON request body declares a real property called "body", as opposed to
+   * the "body" wrapper the parser adds for non-object request bodies
+   */
+  private declaresBodyProperty(operation: OpenAPIV3.OperationObject): boolean {
+    const requestBody = this.resolveRef(operation.requestBody) as OpenAPIV3.RequestBodyObject | undefined;
+    const schema = this.resolveRef(requestBody?.content?.["application/json"]?.schema) as OpenAPIV3.SchemaObject;
+    return Boolean(schema?.properties && "body" in schema.properties);
+  }
+
+  // Returns whether apiKeyFile held a different key than the one in use. Basic auth takes precedence
+  // over the API key, so the file is not read with it
+  private reloadApiKey(): boolean {
+    if (!this.config.apiKeyFile || this.config.basicAuth) {
+      return false;
+    }
+    let apiKey: string;
+    try {
+      apiKey = readApiKeyFile(this.config.apiKeyFile);
+    } catch (error) {
+      console.error("Failed to read the API key file:", error instanceof Error ? error.message : error);
+      return false;
+    }
+    if (!apiKey || apiKey === (this.refreshedApiKey ?? this.config.apiKey)) {
+      return false;
+    }
+    this.refreshedApiKey = apiKey;
+    return true;
+  }
+
+  // Only JSON bodies past the threshold are worth compressing, and only while the server accepts them
+  private shouldCompress(body: unknown): boolean {
+    if (!this.config.compressRequest || this.compressionRejected || typeof body !== "object" || body === null) {
+      return false;
+    }
+    const minBytes = this.config.compressRequestMinBytes ?? DEFAULT_COMPRESS_REQUEST_MIN_BYTES;
+    return Buffer.byteLength(JSON.stringify(body)) >= minBytes;
+  }
+
+  /**
+   * The content type of a request body declared as a binary string (type: string, format: binary),
+   * which is sent as raw bytes instead of JSON. Multipart bodies are handled by prepareFileUpload.
+   */
+  private binaryBodyContentType(operation: OpenAPIV3.OperationObject): string | undefined {
+    const requestBody = this.resolveRef(operation.requestBody) as OpenAPIV3.RequestBodyObject | undefined;
+    const content = requestBody?.content ?? {};
+    if (content["multipart/form-data"]) {
+      return undefined;
+    }
+    return Object.entries(content).find(([, media]) => {
+      const schema = this.resolveRef(media.schema) as OpenAPIV3.SchemaObject | undefined;
+      return schema?.type === "string" && schema.format === "binary";
+    })?.[0];
   }
 
   private async prepareFileUpload(
     operation: OpenAPIV3.OperationObject,
     params: Record<string, any>,
   ): Promise<FormData | null> {
-    console.error("prepareFileUpload", { operation, params });
+    // Arguments may carry credentials such as cookie values, so only the operation is logged
+    console.error("prepareFileUpload", operation.operationId);
     const fileParams = isFileUploadParameter(operation);
-    if (fileParams.length === 0) return null;
+    const inlineFiles: MultipartFile[] | undefined = Array.isArray(params._files) ? params._files : undefined;
+    const jsonPart: MultipartJsonPart | undefined = params._json_part;
+    if (fileParams.length === 0 && !inlineFiles && !jsonPart) return null;
 
     const formData = new FormData();
 
+    // Handle inline files, each added as its own part under its declared field name
+    for (const file of inlineFiles ?? []) {
+      if (!file?.field || typeof file.data !== "string") {
+        throw new Error("Each entry in _files must have a field and base64-encoded data");
+      }
+      formData.append(file.field, Buffer.from(file.data, "base64"), {
+        filename: file.filename,
+        ...(file.content_type ? { contentType: file.content_type } : {}),
+      });
+    }
+
+    // Handle a JSON part, sent with its own content type rather than as a plain text field
+    if (jsonPart) {
+      if (!jsonPart.field || jsonPart.value === undefined) {
+        throw new Error("_json_part must have a field and a value");
+      }
+      formData.append(jsonPart.field, JSON.stringify(jsonPart.value), { contentType: "application/json" });
+    }
+
     // Handle file uploads
     for (const param of fileParams) {
-      console.error(`extracting ${param}`, { params });
+      console.error(`extracting ${param}`);
       const filePath = params[param];
       if (!filePath) {
         throw new Error(`File path must be provided for parameter: ${param}`);
@@ -96,7 +530,7 @@ export class HttpClient {
 
     // Add non-file parameters to form data
     for (const [key, value] of Object.entries(params)) {
-      if (!fileParams.includes(key)) {
+      if (!fileParams.includes(key) && key !== "_files" && key !== "_json_part") {
         formData.append(key, value);
       }
     }
@@ -104,6 +538,98 @@ export class HttpClient {
     return formData;
   }
 
+  /**
+   * Fail fast while the circuit of an operation is open. Once the cooldown has passed the circuit is
+   * half-open: the next call goes through, and a single failure opens it again.
+   */
+  // Returns whether the call is the trial call of a half-open circuit, which the other calls wait out
+  private checkCircuit(operationId: string): boolean {
+    const circuit = this.circuits.get(operationId);
+    if (!circuit || circuit.failures < (this.config.circuitBreakerThreshold ?? Infinity)) {
+      return false;
+    }
+    if (circuit.openUntil > Date.now()) {
+      throw new CircuitOpenError(operationId, circuit.openUntil - Date.now());
+    }
+    if (circuit.trialInFlight) {
+      throw new CircuitOpenError(operationId, 0);
+    }
+    circuit.trialInFlight = true;
+    return true;
+  }
+
+  // Server errors and network failures count against the circuit, client errors do not
+  private recordOutcome(operationId: string, error?: any) {
+    const threshold = this.config.circuitBreakerThreshold;
+    if (!threshold) {
+      return;
+    }
+    if (error && (!error.response || error.response.status >= 500)) {
+      const circuit = this.circuits.get(operationId) ?? { failures: 0, openUntil: 0 };
+      circuit.failures++;
+      if (circuit.failures >= threshold) {
+        circuit.openUntil = Date.now() + (this.config.circuitBreakerCooldownMs ?? DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS);
+        console.error(`Opening circuit for ${operationId} after ${circuit.failures} consecutive failures`);
+      }
+      this.circuits.set(operationId, circuit);
+    } else {
+      this.circuits.delete(operationId);
+    }
+  }
+
+  /**
+   * Execute a paginated operation and fetch every page, following the cursor in each response until
+   * it is empty. Array fields of the pages are concatenated into the first page, and the cursor field
+   * of the result is the one of the last page fetched, so it is only set when maxPages was hit.
+   */
+  async executeOperationAll(
+    operation: OpenAPIV3.OperationObject & { method: string; path: string },
+    params: Record<string, any> = {},
+    { cursorField = "next_cursor", cursorParam = "cursor", maxPages = DEFAULT_MAX_PAGES }: PaginationOptions = {},
+  ): Promise<HttpClientResponse> {
+    let response = await this.executeOperation(operation, params);
+    if (!isPlainObject(response.data)) {
+      return response;
+    }
+    const combined: Record<string, any> = { ...response.data };
+    for (let page = 1; page < maxPages && response.data?.[cursorField]; page++) {
+      response = await this.executeOperation(operation, { ...params, [cursorParam]: response.data[cursorField] });
+      for (const [key, value] of Object.entries(response.data ?? {})) {
+        if (Array.isArray(value) && Array.isArray(combined[key])) {
+          combined[key] = [...combined[key], ...value];
+        }
+      }
+      combined[cursorField] = response.data?.[cursorField];
+    }
+    if (response.data?.[cursorField]) {
+      console.error(`Stopped following ${operation.operationId} pages after ${maxPages} pages`);
+    }
+    return { ...response, data: combined };
+  }
+
+  /**
+   * Forget the cached ETags and bodies, so the next GETs fetch fresh responses
+   */
+  clearCache() {
+    this.etagCache.clear();
+  }
+
+  /**
+   * Open a connection to the API ahead of the first tool call. Failures are logged, never thrown.
+   */
+  async warmup(): Promise<void> {
+    if (this.config.offline) {
+      return;
+    }
+    try {
+      const api = await this.api;
+      await api.head(this.config.healthPath ?? DEFAULT_HEALTH_PATH, { validateStatus: () => true });
+      console.error("Warmed up connection to", this.config.baseUrl);
+    } catch (error) {
+      console.error("Connection warmup failed:", error instanceof Error ? error.message : error);
+    }
+  }
+
   /**
    * Execute an OpenAPI operation
    */
@@ -111,11 +637,69 @@ export class HttpClient {
     operation: OpenAPIV3.OperationObject & { method: string; path: string },
     params: Record<string, any> = {},
   ): Promise<HttpClientResponse<T>> {
-    const api = await this.api;
     const operationId = operation.operationId;
     if (!operationId) {
       throw new Error("Operation ID is required");
     }
+    if (this.config.offline) {
+      throw new Error(`Offline mode is on, not calling the API for ${operationId}`);
+    }
+    const trial = this.checkCircuit(operationId);
+    try {
+      return await this.sendOperation<T>(operation, operationId, params);
+    } finally {
+      const circuit = trial ? this.circuits.get(operationId) : undefined;
+      if (circuit) {
+        circuit.trialInFlight = false;
+      }
+    }
+  }
+
+  // Sends the request of executeOperation, and once more when it is worth retrying right away
+  private async sendOperation<T = any>(
+    operation: OpenAPIV3.OperationObject & { method: string; path: string },
+    operationId: string,
+    params: Record<string, any>,
+  ): Promise<HttpClientResponse<T>> {
+    const api = await this.api;
+    const requestParams = params;
+
+    // The special _range argument is forwarded as a Range header, e.g. "bytes=0-99" to resume a download
+    let range: string | undefined;
+    if ("_range" in params) {
+      const { _range, ...rest } = params;
+      range = typeof _range === "string" ? _range : undefined;
+      params = rest;
+    }
+
+    // The special _prefer argument is forwarded as a Prefer header, e.g. "return=minimal"
+    let prefer =
+      this.config.preferRepresentation && operation.method?.toLowerCase() === "post"
+        ? "return=representation"
+        : undefined;
+    if ("_prefer" in params) {
+      const { _prefer, ...rest } = params;
+      prefer = typeof _prefer === "string" ? _prefer : prefer;
+      params = rest;
+    }
+
+    // Parameters pinned to a single value with const are filled in when omitted
+    for (const param of operation.parameters ?? []) {
+      if ("name" in param && param.schema && "const" in param.schema && params[param.name] === undefined) {
+        params = { ...params, [param.name]: param.schema.const };
+      }
+    }
+
+    // Query parameters the caller omits are sent with their declared default, if any
+    for (const param of operation.parameters ?? []) {
+      if (!("name" in param) || param.in !== "query" || params[param.name] !== undefined) {
+        continue;
+      }
+      const schema = this.resolveRef(param.schema) as OpenAPIV3.SchemaObject | undefined;
+      if (schema?.default !== undefined) {
+        params = { ...params, [param.name]: schema.default };
+      }
+    }
 
     // Handle file uploads if present
     const formData = await this.prepareFileUpload(operation, params);
@@ -123,14 +707,27 @@ export class HttpClient {
     // Separate parameters based on their location
     const urlParameters: Record<string, any> = {};
     const bodyParams: Record<string, any> = formData || { ...params };
+    const cookies: string[] = [];
 
-    // Extract path and query parameters based on operation definition
+    // Extract path, query and cookie parameters based on operation definition
     if (operation.parameters) {
       for (const param of operation.parameters) {
         if ("name" in param && param.name && param.in) {
-          if (param.in === "path" || param.in === "query") {
+          if (param.in === "cookie") {
             if (params[param.name] !== undefined) {
-              urlParameters[param.name] = params[param.name];
+              cookies.push(`${param.name}=${encodeURIComponent(String(params[param.name]))}`);
+              if (!formData) {
+                delete bodyParams[param.name];
+              }
+            }
+          } else if (param.in === "path" || param.in === "query") {
+            if (params[param.name] !== undefined) {
+              const value = params[param.name];
+              if (param.in === "query" && isPlainObject(value)) {
+                Object.assign(urlParameters, serializeQueryObject(param, value));
+              } else {
+                urlParameters[param.name] = value;
+              }
               if (!formData) {
                 delete bodyParams[param.name];
               }
@@ -140,7 +737,8 @@ export class HttpClient {
       }
     }
 
-    // Add all parameters as url parameters if there is no requestBody defined
+    // Add all parameters as url parameters if there is no requestBody defined. This holds for every method,
+    // so a DELETE declaring a request body (e.g. bulk delete) sends a JSON body, and one without it a query string
     if (!operation.requestBody && !formData) {
       for (const key in bodyParams) {
         if (bodyParams[key] !== undefined) {
@@ -150,6 +748,20 @@ export class HttpClient {
       }
     }
 
+    // Accept the body both wrapped in the "body" argument and with its fields passed flat
+    let payload: any = bodyParams;
+    const binaryContentType = formData ? undefined : this.binaryBodyContentType(operation);
+    if (binaryContentType) {
+      // Binary bodies are passed base64-encoded and sent as raw bytes
+      if (typeof bodyParams.body !== "string") {
+        throw new Error(`Operation ${operationId} expects its body as a base64-encoded string`);
+      }
+      payload = Buffer.from(bodyParams.body, "base64");
+    } else if (!formData && operation.requestBody && "body" in bodyParams && !this.declaresBodyProperty(operation)) {
+      const { body, ...rest } = bodyParams;
+      payload = isPlainObject(body) ? { ...rest, ...body } : body;
+    }
+
     const operationFn = (api as any)[operationId];
     if (!operationFn) {
       throw new Error(`Operation ${operationId} not found`);
@@ -157,21 +769,102 @@ export class HttpClient {
 
     try {
       // If we have form data, we need to set the correct headers
-      const hasBody = Object.keys(bodyParams).length > 0;
+      const hasBody = payload === bodyParams ? Object.keys(bodyParams).length > 0 : payload !== undefined;
       const headers = formData
         ? formData.getHeaders()
-        : { ...(hasBody ? { "Content-Type": "application/json" } : { "Content-Type": null }) };
-      const requestConfig = {
+        : { ...(hasBody ? { "Content-Type": binaryContentType ?? "application/json" } : { "Content-Type": null }) };
+      const requestConfig: { headers: Record<string, any>; [key: string]: any } = {
         headers: {
           ...headers,
         },
       };
+      if (cookies.length > 0) {
+        requestConfig.headers.Cookie = cookies.join("; ");
+      }
+      if (range) {
+        requestConfig.headers.Range = range;
+      }
+      if (prefer) {
+        requestConfig.headers.Prefer = prefer;
+      }
+      if (this.tokenSource) {
+        requestConfig.headers.Authorization = `Bearer ${await this.tokenSource.getToken()}`;
+      } else if (this.refreshedApiKey) {
+        requestConfig.headers.Authorization = `Bearer ${this.refreshedApiKey}`;
+      }
+
+      let body = hasBody ? payload : undefined;
+      const compressed = body !== undefined && !formData && !binaryContentType && this.shouldCompress(body);
+      if (compressed) {
+        body = gzipSync(JSON.stringify(body));
+        requestConfig.headers["Content-Encoding"] = "gzip";
+      }
+
+      preserveHeaderCase(requestConfig.headers, this.config.caseSensitiveHeaders ?? []);
+
+      // A tool override may have moved the operation to another method or path than the spec declares
+      const declared = this.declaredRoute(operationId);
+      if (declared && operation.method && declared.method.toLowerCase() !== operation.method.toLowerCase()) {
+        requestConfig.method = operation.method.toLowerCase();
+      }
+      // Path values are encoded here as single path segments. The URL built from them replaces the one
+      // openapi-client-axios builds whenever encoding changes a value, so that each is encoded exactly once
+      const moved =
+        declared && operation.path && declared.path.replace(/^\/+/, "") !== operation.path.replace(/^\/+/, "");
+      const pathValues = [...(operation.path ?? "").matchAll(/\{([^}]+)\}/g)]
+        .map(([, name]) => urlParameters[name])
+        .filter((value) => value !== undefined);
+      if (moved || pathValues.some((value) => encodeURIComponent(String(value)) !== String(value))) {
+        requestConfig.url = `/${operation.path.replace(/^\/+/, "")}`.replace(/\{([^}]+)\}/g, (match, name) =>
+          urlParameters[name] !== undefined ? encodeURIComponent(String(urlParameters[name])) : match,
+        );
+      }
+
+      // Revalidate a previously cached GET response with its ETag. A GET declaring a body, e.g. a search,
+      // is cached per body as well
+      const cacheKey =
+        this.config.etagCache && operation.method?.toLowerCase() === "get"
+          ? `${operationId}:${JSON.stringify(urlParameters)}${hasBody ? `:${JSON.stringify(payload)}` : ""}`
+          : null;
+      const cached = cacheKey ? this.etagCache.get(cacheKey) : undefined;
+      if (cached) {
+        requestConfig.headers["If-None-Match"] = cached.etag;
+      }
+
+      // Besides 2xx, a status is a success when the operation declares it, e.g. a 302 pointing to an export
+      const extraSuccessCodes = Object.keys(operation.responses ?? {})
+        .filter((code) => /^[13]\d\d$/.test(code))
+        .map(Number);
+      if (cached) {
+        extraSuccessCodes.push(304);
+      }
+      if (extraSuccessCodes.length > 0) {
+        // Redirects stay successes when they are not followed, as with the client-wide default
+        const lastSuccess = this.config.maxRedirects === 0 ? 400 : 300;
+        requestConfig.validateStatus = (status: number) =>
+          (status >= 200 && status < lastSuccess) || extraSuccessCodes.includes(status);
+      }
 
       // first argument is url parameters, second is body parameters
-      console.error("calling operation", { operationId, urlParameters, bodyParams, requestConfig });
-      const response = await operationFn(urlParameters, hasBody ? bodyParams : undefined, requestConfig);
+      // Headers carry credentials, so they are only logged redacted and at trace level
+      console.error("calling operation", { operationId, urlParameters, bodyParams: payload });
+      traceBody(`request headers for ${operationId}:`, requestConfig.headers);
+      traceBody(`request body for ${operationId}:`, formData ? "<multipart form data>" : hasBody ? payload : undefined);
+      const response = await operationFn(urlParameters, body, requestConfig);
+      this.recordOutcome(operationId);
 
       console.error("operation finished");
+      traceBody(`response body for ${operationId}:`, response.data);
+
+      let data = response.data;
+      if (cacheKey) {
+        if (response.status === 304 && cached) {
+          data = cached.data;
+        } else if (response.headers?.etag) {
+          this.etagCache.set(cacheKey, { etag: response.headers.etag, data: response.data });
+        }
+      }
+
       // Convert axios headers to Headers object
       const responseHeaders = new Headers();
       Object.entries(response.headers).forEach(([key, value]) => {
@@ -179,13 +872,28 @@ export class HttpClient {
       });
 
       return {
-        data: response.data,
+        data,
         status: response.status,
         headers: responseHeaders,
       };
     } catch (error: any) {
+      // A server that does not accept gzip bodies answers 415, so stop compressing and send this one again as is
+      if (compressed && error.response?.status === 415) {
+        console.error("Server does not accept compressed request bodies, sending them uncompressed from now on");
+        this.compressionRejected = true;
+        return this.sendOperation(operation, operationId, requestParams);
+      }
+      // An expired key may have been replaced in the key file meanwhile, so retry once if it changed
+      if (error.response?.status === 401 && this.reloadApiKey()) {
+        console.error(`${operationId} was rejected with 401, retrying with the API key from the key file`);
+        return this.sendOperation(operation, operationId, requestParams);
+      }
+      this.recordOutcome(operationId, error);
       if (error.response) {
-        console.error("Error in http client", error);
+        // The error carries the request config and with it the Authorization header, so only a summary is logged
+        const { status, statusText } = error.response;
+        console.error(`Error in http client: ${operationId} failed with ${status}`, statusText ?? "");
+        traceBody(`error response body for ${operationId}:`, error.response.data);
         const headers = new Headers();
         Object.entries(error.response.headers).forEach(([key, value]) => {
           if (value) headers.append(key, value.toString());
@@ -0,0 +1,92 @@
+import axios, { AxiosError, AxiosHeaders, type AxiosAdapter, type AxiosResponse } from "axios";
+import http2 from "node:http2";
+
+// Connection-specific headers are not allowed on HTTP/2 requests
+const CONNECTION_HEADERS = ["connection", "host", "keep-alive", "proxy-connection", "transfer-encoding", "upgrade"];
+
+/**
+ * Axios adapter that talks HTTP/2 to the API without negotiating it first ("prior knowledge"),
+ * which lets a local API served over cleartext HTTP/2 skip the upgrade round trip.
+ * One session per origin is kept open and reused across requests. The options are passed on to
+ * http2.connect, e.g. the minimum TLS version for https origins.
+ */
+export function createHttp2Adapter(options: http2.SecureClientSessionOptions = {}): AxiosAdapter {
+  const sessions = new Map<string, http2.ClientHttp2Session>();
+
+  function getSession(origin: string): http2.ClientHttp2Session {
+    const existing = sessions.get(origin);
+    if (existing && !existing.closed && !existing.destroyed) {
+      return existing;
+    }
+    const session = http2.connect(origin, options);
+    session.on("error", () => sessions.delete(origin));
+    session.on("close", () => sessions.delete(origin));
+    // Don't keep the process alive just for an idle session
+    session.unref();
+    sessions.set(origin, session);
+    return session;
+  }
+
+  return (config) =>
+    new Promise<AxiosResponse>((resolve, reject) => {
+      const url = new URL(axios.getUri(config));
+      const headers: http2.OutgoingHttpHeaders = {
+        ":method": (config.method ?? "get").toUpperCase(),
+        ":path": `${url.pathname}${url.search}`,
+      };
+      for (const [name, value] of Object.entries(AxiosHeaders.from(config.headers).toJSON())) {
+        if (value !== undefined && value !== null && !CONNECTION_HEADERS.includes(name.toLowerCase())) {
+          headers[name.toLowerCase()] = value as string;
+        }
+      }
+
+      let request: http2.ClientHttp2Stream;
+      try {
+        request = getSession(url.origin).request(headers);
+      } catch (error) {
+        reject(AxiosError.from(error, undefined, config));
+        return;
+      }
+
+      if (config.timeout) {
+        request.setTimeout(config.timeout, () => {
+          request.close(http2.constants.NGHTTP2_CANCEL);
+          reject(new AxiosError(`timeout of ${config.timeout}ms exceeded`, AxiosError.ECONNABORTED, config, request));
+        });
+      }
+
+      const chunks: Buffer[] = [];
+      let status = 0;
+      let responseHeaders: Record<string, string | string[] | undefined> = {};
+      request.on("response", (received) => {
+        status = Number(received[":status"]);
+        responseHeaders = Object.fromEntries(Object.entries(received).filter(([name]) => !name.startsWith(":")));
+      });
+      request.on("data", (chunk: Buffer) => chunks.push(chunk));
+      request.on("error", (error) => reject(AxiosError.from(error, undefined, config, request)));
+      request.on("end", () => {
+        const body = Buffer.concat(chunks);
+        const response: AxiosResponse = {
+          data: config.responseType === "arraybuffer" ? body : body.toString("utf8"),
+          status,
+          statusText: "",
+          headers: AxiosHeaders.from(responseHeaders as Record<string, string>),
+          config,
+          request,
+        };
+        if (!config.validateStatus || config.validateStatus(status)) {
+          resolve(response);
+          return;
+        }
+        const code = status >= 500 ? AxiosError.ERR_BAD_RESPONSE : AxiosError.ERR_BAD_REQUEST;
+        reject(new AxiosError(`Request failed with status code ${status}`, code, config, request, response));
+      });
+
+      const data = config.data;
+      if (data && typeof data.pipe === "function") {
+        data.pipe(request);
+      } else {
+        request.end(data);
+      }
+    });
+}
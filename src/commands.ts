@@ -0,0 +1,334 @@
+import fs from "node:fs";
+import { OpenAPIV3 } from "openapi-types";
+import { Config, loadConfig, loadConfigStrict } from "./config";
+import { loadOpenApiSpec, resolveSpecSource, specLoadOptions } from "./init-server";
+import { MCPProxy, SERVER_VERSION } from "./mcp/proxy";
+import { OpenAPIToMCPConverter } from "./openapi/parser";
+
+export type ListToolsOptions = {
+  verbose?: boolean;
+  // Only list tools for this HTTP method, e.g. GET
+  method?: string;
+};
+
+export type CliFlags = {
+  json?: boolean;
+  strict?: boolean;
+  verbose?: boolean;
+  method?: string;
+  pretty?: boolean;
+  compact?: boolean;
+};
+
+// The commands each flag applies to
+const FLAG_COMMANDS: Record<keyof CliFlags, string[]> = {
+  json: ["validate"],
+  strict: ["validate"],
+  verbose: ["list-tools"],
+  method: ["list-tools"],
+  pretty: ["run"],
+  compact: ["run"],
+};
+
+/**
+ * Reject flag combinations that contradict each other or that the command would silently ignore
+ */
+export function checkFlagConflicts(command: string, flags: CliFlags) {
+  if (flags.pretty && flags.compact) {
+    throw new Error("--pretty and --compact cannot be used together, pick one");
+  }
+  for (const [flag, commands] of Object.entries(FLAG_COMMANDS)) {
+    if (flags[flag as keyof CliFlags] && !commands.includes(command)) {
+      throw new Error(`--${flag} only applies to ${commands.join(", ")}, not "${command}"`);
+    }
+  }
+}
+
+function formatTable(header: string[], rows: string[][]): string {
+  const widths = header.map((title, i) => Math.max(title.length, ...rows.map((row) => row[i].length)));
+  const formatRow = (row: string[]) => row.map((cell, i) => cell.padEnd(widths[i])).join("  ");
+  return [header, ...rows].map((row) => formatRow(row).trimEnd()).join("\n");
+}
+
+// Load a spec with the headers a protected one needs, as the server does
+function loadSpec(specPath: string | undefined, config: Config = loadConfig()): Promise<OpenAPIV3.Document> {
+  return loadOpenApiSpec(specPath, specLoadOptions(config, specPath));
+}
+
+// Only the server writes the tool cache, the one-shot commands convert the spec without touching it
+function withoutToolCache(config: Config): Config {
+  const { toolCacheFile: _, ...rest } = config;
+  return rest;
+}
+
+async function loadProxy(specPath?: string, config: Config = loadConfig()): Promise<MCPProxy> {
+  const openApiSpec = await loadSpec(specPath, config);
+  return new MCPProxy("Anytype API", openApiSpec, withoutToolCache(config));
+}
+
+const HTTP_METHODS = ["get", "put", "post", "delete", "patch", "head", "options", "trace"];
+
+/**
+ * Find operations that would produce colliding tools: operationIds used more than once, and
+ * method/path pairs that only differ in slashes or path parameter names
+ */
+export function findCollisions(openApiSpec: OpenAPIV3.Document): string[] {
+  const operationIds = new Map<string, string[]>();
+  const routes = new Map<string, string[]>();
+  for (const [path, pathItem] of Object.entries(openApiSpec.paths ?? {})) {
+    for (const method of HTTP_METHODS) {
+      const operation = pathItem?.[method as OpenAPIV3.HttpMethods];
+      if (!operation) {
+        continue;
+      }
+      const route = `${method.toUpperCase()} ${path}`;
+      if (operation.operationId) {
+        operationIds.set(operation.operationId, [...(operationIds.get(operation.operationId) ?? []), route]);
+      }
+      const normalized = `${method} /${path.replace(/^\/+|\/+$/g, "").replace(/\{[^}]*\}/g, "{}")}`;
+      routes.set(normalized, [...(routes.get(normalized) ?? []), route]);
+    }
+  }
+
+  const collisions: string[] = [];
+  for (const [operationId, usages] of operationIds) {
+    if (usages.length > 1) {
+      collisions.push(`operationId ${operationId} is used by ${usages.join(", ")}`);
+    }
+  }
+  for (const usages of routes.values()) {
+    if (usages.length > 1) {
+      collisions.push(`${usages.join(", ")} resolve to the same route`);
+    }
+  }
+  return collisions;
+}
+
+/**
+ * Print a summary of the tools a spec produces, including operations that failed to convert and
+ * operations that collide. The configuration is checked too, and any malformed variable is reported
+ * as an error. With strict, collisions are errors as well.
+ */
+export async function validateSpec(specPath: string | undefined, json: boolean, strict = false) {
+  const config = loadConfigStrict();
+  const openApiSpec = await loadSpec(specPath, config);
+  const proxy = new MCPProxy("Anytype API", openApiSpec, withoutToolCache(config), resolveSpecSource(specPath));
+  const info = proxy.getServerInfo();
+  const collisions = findCollisions(openApiSpec);
+  if (json) {
+    console.log(JSON.stringify({ ...info, collisions }, null, 2));
+  } else {
+    console.log(`${info.name} ${info.version}: ${info.toolCount} tools`);
+    info.toolNames.forEach((name) => console.log(`  - ${name}`));
+    if (info.skippedOperations.length > 0) {
+      console.log(`\nSkipped ${info.skippedOperations.length} operation(s):`);
+      info.skippedOperations.forEach((op) => console.log(`  - ${op.method.toUpperCase()} ${op.path}: ${op.reason}`));
+    }
+    if (collisions.length > 0) {
+      console.log(`\n${strict ? "Error" : "Warning"}: found ${collisions.length} collision(s):`);
+      collisions.forEach((collision) => console.log(`  - ${collision}`));
+    }
+  }
+  if (strict && collisions.length > 0) {
+    throw new Error(`Spec has ${collisions.length} colliding operation(s)`);
+  }
+}
+
+/**
+ * Print the tool names, or with verbose a table mapping each tool to its HTTP method and path
+ */
+export async function listTools(specPath: string | undefined, { verbose = false, method }: ListToolsOptions = {}) {
+  const proxy = await loadProxy(specPath);
+  const tools = method ? proxy.toolsByMethod(method) : proxy.listTools();
+  if (!verbose) {
+    tools.forEach((tool) => console.log(tool.name));
+    return;
+  }
+
+  const rows = tools.map((tool) => {
+    const operation = proxy.findOperation(tool.name);
+    const description = (tool.description ?? "").split("\n")[0];
+    return [operation?.method.toUpperCase() ?? "", operation?.path ?? "", tool.name, description];
+  });
+  console.log(formatTable(["METHOD", "PATH", "TOOL", "DESCRIPTION"], rows));
+}
+
+export type SpecDiff = {
+  added: string[];
+  removed: string[];
+  // Tools present in both specs whose route or input schema differs, with what changed
+  changed: string[];
+};
+
+type ToolSummary = { route: string; inputSchema: string };
+
+function summarizeTools(openApiSpec: OpenAPIV3.Document, config: Config): Map<string, ToolSummary> {
+  const { tools, openApiLookup } = new OpenAPIToMCPConverter(openApiSpec, {
+    useDefs: config.useDefs,
+    mergeAllOf: config.mergeAllOf,
+    nameCase: config.nameCase,
+  }).convertToMCPTools();
+  const summaries = new Map<string, ToolSummary>();
+  for (const [apiName, def] of Object.entries(tools)) {
+    for (const method of def.methods) {
+      const name = `${apiName}-${method.name}`;
+      const operation = openApiLookup[name];
+      summaries.set(name, {
+        route: operation ? `${operation.method.toUpperCase()} ${operation.path}` : "",
+        inputSchema: JSON.stringify(method.inputSchema),
+      });
+    }
+  }
+  return summaries;
+}
+
+/**
+ * Compare the tools two versions of a spec produce, to anticipate breaking changes before upgrading
+ */
+export function diffSpecs(
+  oldSpec: OpenAPIV3.Document,
+  newSpec: OpenAPIV3.Document,
+  config: Config = loadConfig(),
+): SpecDiff {
+  const before = summarizeTools(oldSpec, config);
+  const after = summarizeTools(newSpec, config);
+  const diff: SpecDiff = { added: [], removed: [], changed: [] };
+  for (const [name, tool] of after) {
+    const previous = before.get(name);
+    if (!previous) {
+      diff.added.push(`${name} (${tool.route})`);
+    } else if (previous.route !== tool.route) {
+      diff.changed.push(`${name}: ${previous.route} -> ${tool.route}`);
+    } else if (previous.inputSchema !== tool.inputSchema) {
+      diff.changed.push(`${name}: input schema changed`);
+    }
+  }
+  for (const [name, tool] of before) {
+    if (!after.has(name)) {
+      diff.removed.push(`${name} (${tool.route})`);
+    }
+  }
+  return diff;
+}
+
+/**
+ * Print the tools added, removed and changed between two specs
+ */
+export async function printSpecDiff(oldSpecPath: string, newSpecPath: string) {
+  const config = loadConfig();
+  const diff = diffSpecs(await loadSpec(oldSpecPath, config), await loadSpec(newSpecPath, config), config);
+  const sections: Array<[string, string, string[]]> = [
+    ["Added", "+", diff.added],
+    ["Removed", "-", diff.removed],
+    ["Changed", "~", diff.changed],
+  ];
+  if (sections.every(([, , tools]) => tools.length === 0)) {
+    console.log("No tool changes");
+    return;
+  }
+  for (const [title, marker, tools] of sections) {
+    if (tools.length > 0) {
+      console.log(`${title} (${tools.length}):`);
+      tools.forEach((tool) => console.log(`  ${marker} ${tool}`));
+    }
+  }
+}
+
+// Advisory findings about a spec, each listing the operations affected. None of them stops the server
+export type SpecLint = {
+  // Such operations are skipped rather than turned into tools
+  missingOperationIds: string[];
+  // Operations with neither a summary nor a description, whose tools are listed without one
+  missingDescriptions: string[];
+  missingResponses: string[];
+};
+
+/**
+ * Find operations that make for missing or poor tools for lack of an operationId, a description or
+ * declared responses
+ */
+export function lintSpec(openApiSpec: OpenAPIV3.Document): SpecLint {
+  const lint: SpecLint = { missingOperationIds: [], missingDescriptions: [], missingResponses: [] };
+  for (const [path, pathItem] of Object.entries(openApiSpec.paths ?? {})) {
+    for (const method of HTTP_METHODS) {
+      const operation = pathItem?.[method as OpenAPIV3.HttpMethods];
+      if (!operation) {
+        continue;
+      }
+      const route = `${method.toUpperCase()} ${path}`;
+      if (!operation.operationId) {
+        lint.missingOperationIds.push(route);
+      }
+      if (!operation.summary && !operation.description) {
+        lint.missingDescriptions.push(route);
+      }
+      if (Object.keys(operation.responses ?? {}).length === 0) {
+        lint.missingResponses.push(route);
+      }
+    }
+  }
+  return lint;
+}
+
+/**
+ * Print the lint findings of a spec with their counts
+ */
+export async function printSpecLint(specPath: string | undefined) {
+  const lint = lintSpec(await loadSpec(specPath));
+  const sections: Array<[string, string[]]> = [
+    ["Missing operationId", lint.missingOperationIds],
+    ["Missing description", lint.missingDescriptions],
+    ["No declared responses", lint.missingResponses],
+  ];
+  const total = sections.reduce((count, [, routes]) => count + routes.length, 0);
+  if (total === 0) {
+    console.log("No issues found");
+    return;
+  }
+  for (const [title, routes] of sections) {
+    if (routes.length > 0) {
+      console.log(`${title} (${routes.length}):`);
+      routes.forEach((route) => console.log(`  - ${route}`));
+    }
+  }
+  console.log(`\n${total} finding(s), none of them fatal`);
+}
+
+/**
+ * Print the input schema of one tool as indented JSON, to help craft its arguments
+ */
+export async function printToolSchema(specPath: string | undefined, toolName: string) {
+  const proxy = await loadProxy(specPath);
+  const tool = proxy.listTools().find((candidate) => candidate.name === toolName);
+  if (!tool) {
+    throw new Error(`Unknown tool "${toolName}", run list-tools to see the available ones`);
+  }
+  console.log(JSON.stringify(tool.inputSchema, null, 2));
+}
+
+/**
+ * Print the version along with where it was built from, for bug reports. The commit is stamped in by
+ * the CLI build, and the spec title and version are included when a spec path is given.
+ */
+export async function printVersion(specPath?: string) {
+  console.log(`anytype-mcp ${SERVER_VERSION}`);
+  console.log(`commit ${process.env.ANYTYPE_MCP_GIT_COMMIT || "unknown"}`);
+  console.log(`node ${process.version}`);
+  if (specPath) {
+    const { info } = await loadSpec(specPath);
+    console.log(`spec ${info.title} ${info.version}`);
+  }
+}
+
+/**
+ * Delete the tool cache file, so the next start converts the spec again. A running server
+ * clears its response cache on SIGHUP instead.
+ */
+export function clearToolCache(config: Config = loadConfig()) {
+  if (!config.toolCacheFile) {
+    console.log("No tool cache is configured, set OPENAPI_MCP_TOOL_CACHE to use one");
+    return;
+  }
+  fs.rmSync(config.toolCacheFile, { force: true });
+  console.log(`Cleared tool cache ${config.toolCacheFile}`);
+}
@@ -0,0 +1,515 @@
+import fs from "node:fs";
+import { NAME_CASES, type NameCase } from "./openapi/parser";
+
+export type BasicAuthCredentials = {
+  username: string;
+  password: string;
+};
+
+// OAuth2 client credentials for APIs fronted by a gateway that issues bearer tokens
+export type OAuthClientCredentials = {
+  tokenUrl: string;
+  clientId: string;
+  clientSecret: string;
+  scopes?: string[];
+};
+
+// Where the local Anytype API listens
+export const DEFAULT_BASE_URL = "http://localhost:31009";
+
+// Requested to check that the API is reachable
+export const DEFAULT_HEALTH_PATH = "/health";
+
+/**
+ * Corrections applied to a converted tool, for working around mistakes in the spec
+ */
+export type ToolOverride = {
+  method?: string;
+  path?: string;
+  description?: string;
+};
+
+export const RESULT_FORMATS = ["json", "ndjson"] as const;
+export type ResultFormat = (typeof RESULT_FORMATS)[number];
+
+export const TLS_VERSIONS = ["1.2", "1.3"] as const;
+export type TlsVersion = (typeof TLS_VERSIONS)[number];
+
+/**
+ * Server configuration, read from ANYTYPE_API_KEY and OPENAPI_MCP_* environment variables
+ */
+export type Config = {
+  apiKey?: string;
+  // File holding the API key, read on startup when ANYTYPE_API_KEY is unset and again when the API answers 401
+  apiKeyFile?: string;
+  // Used when the spec declares no servers, DEFAULT_BASE_URL otherwise
+  defaultBaseUrl?: string;
+  // Values for the {variables} in the server URL of the spec, replacing their defaults
+  serverVariables?: Record<string, string>;
+  // Used for connection checks, DEFAULT_HEALTH_PATH otherwise
+  healthPath?: string;
+  basicAuth?: BasicAuthCredentials;
+  // Fetch bearer tokens with the client credentials grant instead of sending the API key
+  oauth?: OAuthClientCredentials;
+  // Refuse to start without an API key, basic auth credentials or an Authorization header
+  requireApiKey?: boolean;
+  // Refuse to start when the configured Anytype-Version header differs from the version of the spec
+  strictApiVersion?: boolean;
+  // Sent as Accept-Language with every request
+  acceptLanguage?: string;
+  etagCache?: boolean;
+  http2PriorKnowledge?: boolean;
+  // Refuse HTTPS connections to the API that can't negotiate at least this TLS version
+  minTlsVersion?: TlsVersion;
+  // Open a connection to the API on startup, before the first tool call
+  warmupOnStart?: boolean;
+  // Share component schemas through $defs in tool input schemas instead of inlining them
+  useDefs?: boolean;
+  // Merge allOf object subschemas into a single object schema
+  mergeAllOf?: boolean;
+  // Where the converted tools are cached, reused on startup while the spec is unchanged
+  toolCacheFile?: string;
+  // Casing of tool names: preserve (the default), snake or camel
+  nameCase?: NameCase;
+  // Indent tool results instead of returning compact JSON
+  prettyResults?: boolean;
+  // Return the content of results that are an object with only this key, e.g. "data"
+  unwrapResponseKey?: string;
+  // Response headers returned along with the body as { _headers, body }, e.g. Location
+  extractResponseHeaders?: string[];
+  // With ndjson, array results are returned as one JSON value per line. Other results stay JSON
+  resultFormat?: ResultFormat;
+  // Return failed responses as { status, body } results carrying the HTTP status code and the body as it was sent
+  returnErrorsAsResults?: boolean;
+  // Add metadata such as duration_ms to tool results under _meta
+  includeResponseMetadata?: boolean;
+  // List the schema of object responses as the outputSchema of tools, and return those results as structuredContent
+  outputSchemas?: boolean;
+  // Upper bound on the size of an inbound tool call, in bytes
+  maxRequestBytes?: number;
+  // Tool calls handled at once, further calls are rejected as busy until one finishes. Unlimited when unset
+  requestQueueDepth?: number;
+  // Upper bound on the serialized size of a tool result, unlimited when unset
+  maxResultChars?: number;
+  // Redirects followed per request, 0 disables following them
+  maxRedirects?: number;
+  // Fail every tool call without contacting the API, while tools can still be listed and inspected
+  offline?: boolean;
+  // Ask for the created resource in responses to POST requests with Prefer: return=representation
+  preferRepresentation?: boolean;
+  // Gzip JSON request bodies of at least compressRequestMinBytes, for servers that accept Content-Encoding: gzip
+  compressRequest?: boolean;
+  compressRequestMinBytes?: number;
+  // Consecutive failures after which a tool is short-circuited for a cooldown, disabled when unset
+  circuitBreakerThreshold?: number;
+  circuitBreakerCooldownMs?: number;
+  // Header names to send with exactly this casing
+  caseSensitiveHeaders?: string[];
+  // Header names never sent with requests, even when configured or added by default
+  disableDefaultHeaders?: string[];
+  // Values filled in for arguments a tool call omits, keyed by tool name and then parameter name
+  argDefaults?: Record<string, Record<string, string>>;
+  // Descriptions replacing the ones from the spec, keyed by tool name
+  toolDescriptions?: Record<string, string>;
+  // End each tool description with the HTTP method and path it calls
+  appendRouteToDescription?: boolean;
+  // List a __server_info tool returning the info block of the spec and the tool count
+  exposeServerInfoTool?: boolean;
+  // Keyed by tool name
+  toolOverrides?: Record<string, ToolOverride>;
+  // Extra headers for downloading a spec hosted behind authentication
+  specHeaders?: Record<string, string>;
+  // Send the API key or basic auth credentials when downloading a spec from another origin than the API
+  specCredentials?: boolean;
+  // Retries of a spec download after a transient network failure, 3 when unset
+  maxRetries?: number;
+  // Headers read from OPENAPI_MCP_HEADERS_FILE, the ones in OPENAPI_MCP_HEADERS win over them
+  headers?: Record<string, string>;
+};
+
+function parseBasicAuth(value: string, issues: string[]): BasicAuthCredentials | undefined {
+  const separator = value.indexOf(":");
+  if (separator === -1) {
+    issues.push("OPENAPI_MCP_BASIC_AUTH must be in the form username:password");
+    return undefined;
+  }
+  return { username: value.slice(0, separator), password: value.slice(separator + 1) };
+}
+
+function parseJsonObject<T = string>(
+  env: NodeJS.ProcessEnv,
+  name: string,
+  issues: string[],
+): Record<string, T> | undefined {
+  try {
+    const parsed = JSON.parse(env[name]!);
+    if (typeof parsed === "object" && parsed !== null && !Array.isArray(parsed)) {
+      return parsed;
+    }
+    issues.push(`${name} must be a JSON object, got ${Array.isArray(parsed) ? "array" : typeof parsed}`);
+  } catch (error) {
+    issues.push(`${name} is not valid JSON: ${error instanceof Error ? error.message : error}`);
+  }
+  return undefined;
+}
+
+/**
+ * Read an API key from a file holding nothing else, ignoring surrounding whitespace
+ */
+export function readApiKeyFile(filePath: string): string {
+  return fs.readFileSync(filePath, "utf-8").trim();
+}
+
+// A JSON object of headers in a file, in the same format as OPENAPI_MCP_HEADERS
+function readHeadersFile(env: NodeJS.ProcessEnv, issues: string[]): Record<string, string> | undefined {
+  const name = "OPENAPI_MCP_HEADERS_FILE";
+  try {
+    return parseJsonObject({ [name]: fs.readFileSync(env[name]!, "utf-8") }, name, issues);
+  } catch (error) {
+    issues.push(`${name} could not be read: ${error instanceof Error ? error.message : error}`);
+    return undefined;
+  }
+}
+
+function parseBoolean(env: NodeJS.ProcessEnv, name: string, issues: string[]): boolean {
+  const value = env[name]!;
+  const normalized = value.toLowerCase();
+  if (!["1", "true", "yes", "on", "0", "false", "no", "off"].includes(normalized)) {
+    issues.push(`${name} must be a boolean (true or false), got "${value}"`);
+  }
+  return ["1", "true", "yes", "on"].includes(normalized);
+}
+
+function parsePositiveInteger(env: NodeJS.ProcessEnv, name: string, issues: string[]): number | undefined {
+  const value = env[name]!;
+  const parsed = Number(value);
+  if (!Number.isInteger(parsed) || parsed <= 0) {
+    issues.push(`${name} must be a positive integer, got "${value}"`);
+    return undefined;
+  }
+  return parsed;
+}
+
+function parseNonNegativeInteger(env: NodeJS.ProcessEnv, name: string, issues: string[]): number | undefined {
+  const value = env[name]!;
+  const parsed = Number(value);
+  if (!Number.isInteger(parsed) || parsed < 0) {
+    issues.push(`${name} must be a non-negative integer, got "${value}"`);
+    return undefined;
+  }
+  return parsed;
+}
+
+// Replace ${NAME} references with the value of that environment variable
+function interpolateEnv(env: NodeJS.ProcessEnv, value: string, issues: string[]): string {
+  return value.replace(/\$\{(\w+)\}/g, (_match, name: string) => {
+    if (env[name] === undefined) {
+      issues.push(`OPENAPI_MCP_ARG_DEFAULTS refers to ${name}, which is not set`);
+      return "";
+    }
+    return env[name]!;
+  });
+}
+
+function parseChoice<T extends string>(
+  env: NodeJS.ProcessEnv,
+  name: string,
+  choices: readonly T[],
+  issues: string[],
+): T | undefined {
+  const value = env[name]!.toLowerCase();
+  if (!choices.includes(value as T)) {
+    issues.push(`${name} must be one of ${choices.join(", ")}, got "${env[name]}"`);
+    return undefined;
+  }
+  return value as T;
+}
+
+function parseList(env: NodeJS.ProcessEnv, name: string): string[] {
+  return env[name]!.split(",")
+    .map((item) => item.trim())
+    .filter(Boolean);
+}
+
+export class ConfigError extends Error {
+  constructor(public issues: string[]) {
+    super(`Invalid configuration:\n${issues.map((issue) => `  - ${issue}`).join("\n")}`);
+    this.name = "ConfigError";
+  }
+}
+
+// Collects a message into issues for every malformed variable and leaves the matching option unset
+function readConfig(env: NodeJS.ProcessEnv, issues: string[]): Config {
+  const config: Config = {};
+
+  if (env.ANYTYPE_API_KEY) {
+    config.apiKey = env.ANYTYPE_API_KEY;
+  }
+
+  if (env.OPENAPI_MCP_API_KEY_FILE) {
+    config.apiKeyFile = env.OPENAPI_MCP_API_KEY_FILE;
+    if (!config.apiKey) {
+      try {
+        config.apiKey = readApiKeyFile(config.apiKeyFile);
+      } catch (error) {
+        issues.push(`OPENAPI_MCP_API_KEY_FILE could not be read: ${error instanceof Error ? error.message : error}`);
+      }
+    }
+  }
+
+  if (env.OPENAPI_MCP_DEFAULT_BASE_URL) {
+    config.defaultBaseUrl = env.OPENAPI_MCP_DEFAULT_BASE_URL;
+  }
+
+  if (env.OPENAPI_MCP_SERVER_VARIABLES) {
+    config.serverVariables = parseJsonObject(env, "OPENAPI_MCP_SERVER_VARIABLES", issues);
+  }
+
+  if (env.OPENAPI_MCP_HEALTH_PATH) {
+    config.healthPath = env.OPENAPI_MCP_HEALTH_PATH;
+  }
+
+  if (env.OPENAPI_MCP_BASIC_AUTH) {
+    config.basicAuth = parseBasicAuth(env.OPENAPI_MCP_BASIC_AUTH, issues);
+  }
+
+  if (env.OPENAPI_MCP_OAUTH_TOKEN_URL || env.OPENAPI_MCP_OAUTH_CLIENT_ID || env.OPENAPI_MCP_OAUTH_CLIENT_SECRET) {
+    const { OPENAPI_MCP_OAUTH_TOKEN_URL: tokenUrl, OPENAPI_MCP_OAUTH_CLIENT_ID: clientId } = env;
+    const clientSecret = env.OPENAPI_MCP_OAUTH_CLIENT_SECRET;
+    if (tokenUrl && clientId && clientSecret) {
+      const scopes = env.OPENAPI_MCP_OAUTH_SCOPES ? parseList(env, "OPENAPI_MCP_OAUTH_SCOPES") : undefined;
+      config.oauth = { tokenUrl, clientId, clientSecret, ...(scopes ? { scopes } : {}) };
+    } else {
+      issues.push(
+        "OPENAPI_MCP_OAUTH_TOKEN_URL, OPENAPI_MCP_OAUTH_CLIENT_ID and OPENAPI_MCP_OAUTH_CLIENT_SECRET " +
+          "must be set together",
+      );
+    }
+  }
+
+  if (env.OPENAPI_MCP_ACCEPT_LANGUAGE) {
+    config.acceptLanguage = env.OPENAPI_MCP_ACCEPT_LANGUAGE;
+  }
+
+  if (env.OPENAPI_MCP_ETAG_CACHE) {
+    config.etagCache = parseBoolean(env, "OPENAPI_MCP_ETAG_CACHE", issues);
+  }
+
+  if (env.OPENAPI_MCP_HTTP2_PRIOR_KNOWLEDGE) {
+    config.http2PriorKnowledge = parseBoolean(env, "OPENAPI_MCP_HTTP2_PRIOR_KNOWLEDGE", issues);
+  }
+
+  if (env.OPENAPI_MCP_MIN_TLS_VERSION) {
+    config.minTlsVersion = parseChoice(env, "OPENAPI_MCP_MIN_TLS_VERSION", TLS_VERSIONS, issues);
+  }
+
+  if (env.OPENAPI_MCP_REQUIRE_API_KEY) {
+    config.requireApiKey = parseBoolean(env, "OPENAPI_MCP_REQUIRE_API_KEY", issues);
+  }
+
+  if (env.OPENAPI_MCP_STRICT_API_VERSION) {
+    config.strictApiVersion = parseBoolean(env, "OPENAPI_MCP_STRICT_API_VERSION", issues);
+  }
+
+  if (env.OPENAPI_MCP_WARMUP) {
+    config.warmupOnStart = parseBoolean(env, "OPENAPI_MCP_WARMUP", issues);
+  }
+
+  if (env.OPENAPI_MCP_USE_DEFS) {
+    config.useDefs = parseBoolean(env, "OPENAPI_MCP_USE_DEFS", issues);
+  }
+
+  if (env.OPENAPI_MCP_MERGE_ALL_OF) {
+    config.mergeAllOf = parseBoolean(env, "OPENAPI_MCP_MERGE_ALL_OF", issues);
+  }
+
+  if (env.OPENAPI_MCP_NAME_CASE) {
+    config.nameCase = parseChoice(env, "OPENAPI_MCP_NAME_CASE", NAME_CASES, issues);
+  }
+
+  if (env.OPENAPI_MCP_TOOL_CACHE) {
+    config.toolCacheFile = env.OPENAPI_MCP_TOOL_CACHE;
+  }
+
+  if (env.OPENAPI_MCP_PRETTY_RESULTS) {
+    config.prettyResults = parseBoolean(env, "OPENAPI_MCP_PRETTY_RESULTS", issues);
+  }
+
+  if (env.OPENAPI_MCP_UNWRAP_RESPONSE_KEY) {
+    config.unwrapResponseKey = env.OPENAPI_MCP_UNWRAP_RESPONSE_KEY;
+  }
+
+  if (env.OPENAPI_MCP_RESULT_FORMAT) {
+    config.resultFormat = parseChoice(env, "OPENAPI_MCP_RESULT_FORMAT", RESULT_FORMATS, issues);
+  }
+
+  if (env.OPENAPI_MCP_EXTRACT_RESPONSE_HEADERS) {
+    config.extractResponseHeaders = parseList(env, "OPENAPI_MCP_EXTRACT_RESPONSE_HEADERS");
+  }
+
+  if (env.OPENAPI_MCP_RESPONSE_METADATA) {
+    config.includeResponseMetadata = parseBoolean(env, "OPENAPI_MCP_RESPONSE_METADATA", issues);
+  }
+
+  if (env.OPENAPI_MCP_RETURN_ERRORS_AS_RESULTS) {
+    config.returnErrorsAsResults = parseBoolean(env, "OPENAPI_MCP_RETURN_ERRORS_AS_RESULTS", issues);
+  }
+
+  if (env.OPENAPI_MCP_OUTPUT_SCHEMAS) {
+    config.outputSchemas = parseBoolean(env, "OPENAPI_MCP_OUTPUT_SCHEMAS", issues);
+  }
+
+  if (env.OPENAPI_MCP_MAX_REQUEST_BYTES) {
+    config.maxRequestBytes = parsePositiveInteger(env, "OPENAPI_MCP_MAX_REQUEST_BYTES", issues);
+  }
+
+  if (env.OPENAPI_MCP_REQUEST_QUEUE_DEPTH) {
+    config.requestQueueDepth = parsePositiveInteger(env, "OPENAPI_MCP_REQUEST_QUEUE_DEPTH", issues);
+  }
+
+  if (env.OPENAPI_MCP_MAX_RESULT_CHARS) {
+    config.maxResultChars = parsePositiveInteger(env, "OPENAPI_MCP_MAX_RESULT_CHARS", issues);
+  }
+
+  if (env.OPENAPI_MCP_MAX_REDIRECTS) {
+    config.maxRedirects = parseNonNegativeInteger(env, "OPENAPI_MCP_MAX_REDIRECTS", issues);
+  }
+
+  if (env.OPENAPI_MCP_OFFLINE) {
+    config.offline = parseBoolean(env, "OPENAPI_MCP_OFFLINE", issues);
+  }
+
+  if (env.OPENAPI_MCP_PREFER_REPRESENTATION) {
+    config.preferRepresentation = parseBoolean(env, "OPENAPI_MCP_PREFER_REPRESENTATION", issues);
+  }
+
+  if (env.OPENAPI_MCP_COMPRESS_REQUEST) {
+    config.compressRequest = parseBoolean(env, "OPENAPI_MCP_COMPRESS_REQUEST", issues);
+  }
+
+  if (env.OPENAPI_MCP_COMPRESS_REQUEST_MIN_BYTES) {
+    config.compressRequestMinBytes = parsePositiveInteger(env, "OPENAPI_MCP_COMPRESS_REQUEST_MIN_BYTES", issues);
+  }
+
+  if (env.OPENAPI_MCP_CIRCUIT_BREAKER_THRESHOLD) {
+    config.circuitBreakerThreshold = parsePositiveInteger(env, "OPENAPI_MCP_CIRCUIT_BREAKER_THRESHOLD", issues);
+  }
+
+  if (env.OPENAPI_MCP_CIRCUIT_BREAKER_COOLDOWN_MS) {
+    config.circuitBreakerCooldownMs = parsePositiveInteger(env, "OPENAPI_MCP_CIRCUIT_BREAKER_COOLDOWN_MS", issues);
+  }
+
+  if (env.OPENAPI_MCP_CASE_SENSITIVE_HEADERS) {
+    config.caseSensitiveHeaders = parseList(env, "OPENAPI_MCP_CASE_SENSITIVE_HEADERS");
+  }
+
+  if (env.OPENAPI_MCP_DISABLE_DEFAULT_HEADERS) {
+    config.disableDefaultHeaders = parseList(env, "OPENAPI_MCP_DISABLE_DEFAULT_HEADERS");
+  }
+
+  if (env.OPENAPI_MCP_ARG_DEFAULTS) {
+    const argDefaults = parseJsonObject<Record<string, string>>(env, "OPENAPI_MCP_ARG_DEFAULTS", issues);
+    if (argDefaults) {
+      config.argDefaults = Object.fromEntries(
+        Object.entries(argDefaults).map(([tool, args]) => [
+          tool,
+          Object.fromEntries(
+            Object.entries(args).map(([arg, value]) => [arg, interpolateEnv(env, String(value), issues)]),
+          ),
+        ]),
+      );
+    }
+  }
+
+  if (env.OPENAPI_MCP_TOOL_DESCRIPTIONS) {
+    config.toolDescriptions = parseJsonObject(env, "OPENAPI_MCP_TOOL_DESCRIPTIONS", issues);
+  }
+
+  if (env.OPENAPI_MCP_APPEND_ROUTE_TO_DESCRIPTION) {
+    config.appendRouteToDescription = parseBoolean(env, "OPENAPI_MCP_APPEND_ROUTE_TO_DESCRIPTION", issues);
+  }
+
+  if (env.OPENAPI_MCP_SERVER_INFO_TOOL) {
+    config.exposeServerInfoTool = parseBoolean(env, "OPENAPI_MCP_SERVER_INFO_TOOL", issues);
+  }
+
+  if (env.OPENAPI_MCP_TOOL_OVERRIDES) {
+    config.toolOverrides = parseJsonObject<ToolOverride>(env, "OPENAPI_MCP_TOOL_OVERRIDES", issues);
+  }
+
+  if (env.OPENAPI_MCP_SPEC_HEADERS) {
+    config.specHeaders = parseJsonObject(env, "OPENAPI_MCP_SPEC_HEADERS", issues);
+  }
+
+  if (env.OPENAPI_MCP_SPEC_CREDENTIALS) {
+    config.specCredentials = parseBoolean(env, "OPENAPI_MCP_SPEC_CREDENTIALS", issues);
+  }
+
+  if (env.OPENAPI_MCP_MAX_RETRIES) {
+    config.maxRetries = parseNonNegativeInteger(env, "OPENAPI_MCP_MAX_RETRIES", issues);
+  }
+
+  if (env.OPENAPI_MCP_HEADERS_FILE) {
+    config.headers = readHeadersFile(env, issues);
+  }
+
+  return config;
+}
+
+/**
+ * Load the configuration, warning about and ignoring malformed variables
+ */
+export function loadConfig(env: NodeJS.ProcessEnv = process.env): Config {
+  const issues: string[] = [];
+  const config = readConfig(env, issues);
+  issues.forEach((issue) => console.warn(`${issue}, ignoring it`));
+  return config;
+}
+
+/**
+ * Load the configuration, failing with a ConfigError that names every malformed variable
+ */
+export function loadConfigStrict(env: NodeJS.ProcessEnv = process.env): Config {
+  const issues: string[] = [];
+  const config = readConfig(env, issues);
+  if (issues.length > 0) {
+    throw new ConfigError(issues);
+  }
+  return config;
+}
+
+/**
+ * Load KEY=value lines from a dotenv-style file into env. Blank lines and # comments are skipped,
+ * and variables that are already set win over the file, as with dotenv.
+ */
+export function loadEnvFile(filePath: string, env: NodeJS.ProcessEnv = process.env) {
+  const lines = fs.readFileSync(filePath, "utf-8").split(/\r?\n/);
+  for (const line of lines) {
+    const match = line.match(/^\s*(?:export\s+)?([\w.-]+)\s*=\s*(.*?)\s*$/);
+    if (!match || line.trimStart().startsWith("#")) {
+      continue;
+    }
+    const [, key, rawValue] = match;
+    const quoted = rawValue.match(/^(["'])(.*)\1$/);
+    const value = quoted ? quoted[2] : rawValue.replace(/\s+#.*$/, "");
+    if (env[key] === undefined) {
+      env[key] = value;
+    }
+  }
+}
+
+/**
+ * Select a named profile: variables prefixed with the profile name in upper case and a double
+ * underscore, e.g. CLOUD__OPENAPI_MCP_DEFAULT_BASE_URL for the profile "cloud", replace the
+ * unprefixed ones in env. This lets one environment or env file hold e.g. a local and a cloud setup.
+ */
+export function applyProfile(profile: string, env: NodeJS.ProcessEnv = process.env) {
+  const prefix = `${profile.toUpperCase().replace(/[^A-Z0-9]/g, "_")}__`;
+  const keys = Object.keys(env).filter((key) => key.startsWith(prefix) && key.length > prefix.length);
+  if (keys.length === 0) {
+    throw new Error(`Profile "${profile}" has no variables, expected ones prefixed with ${prefix}`);
+  }
+  for (const key of keys) {
+    env[key.slice(prefix.length)] = env[key];
+  }
+}
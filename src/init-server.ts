@@ -3,6 +3,8 @@ import axios from "axios";
 import fs from "node:fs";
 import path from "node:path";
 import { OpenAPIV3 } from "openapi-types";
+import { getDefaultHeaders } from "./client/http-client";
+import { Config, DEFAULT_BASE_URL, loadConfig } from "./config";
 import { MCPProxy } from "./mcp/proxy";
 
 export class ValidationError extends Error {
@@ -12,39 +14,205 @@ export class ValidationError extends Error {
   }
 }
 
-export async function loadOpenApiSpec(specPath?: string): Promise<OpenAPIV3.Document> {
-  const defaultSpecUrl = "http://localhost:31009/docs/openapi.json";
-  const finalSpec = specPath || defaultSpecUrl;
+/**
+ * Check the parts of a spec every later step relies on, collecting every problem rather than
+ * stopping at the first one
+ */
+export function validateOpenApiSpec(openApiSpec: Partial<OpenAPIV3.Document>): string[] {
+  const errors: string[] = [];
+  if (typeof openApiSpec.openapi !== "string") {
+    errors.push("openapi version is missing");
+  } else if (!openApiSpec.openapi.startsWith("3.")) {
+    errors.push(`openapi version ${openApiSpec.openapi} is not supported, expected 3.x`);
+  }
+  if (!openApiSpec.info?.title) {
+    errors.push("info.title is missing");
+  }
+  if (!openApiSpec.info?.version) {
+    errors.push("info.version is missing");
+  }
+  if (typeof openApiSpec.paths !== "object" || openApiSpec.paths === null) {
+    errors.push("paths is missing");
+  }
+  return errors;
+}
+
+// Connection-level failures worth retrying; HTTP error responses are not retried. ENOTFOUND is not either,
+// as an unknown host, e.g. a mistyped one, stays unknown, unlike a temporary DNS failure (EAI_AGAIN)
+const RETRYABLE_NETWORK_ERRORS = ["ECONNREFUSED", "ECONNRESET", "EAI_AGAIN", "ETIMEDOUT"];
+
+export type LoadSpecOptions = {
+  maxRetries?: number;
+  retryDelayMs?: number;
+  // Sent when downloading the spec from a URL
+  headers?: Record<string, string>;
+  // Read when the spec path is "-", process.stdin otherwise
+  stdin?: NodeJS.ReadableStream;
+};
+
+// The spec path that reads the spec from stdin, for piping it in
+export const STDIN_SPEC_PATH = "-";
+
+async function readStream(stream: NodeJS.ReadableStream): Promise<string> {
+  const chunks: Buffer[] = [];
+  for await (const chunk of stream) {
+    chunks.push(Buffer.isBuffer(chunk) ? chunk : Buffer.from(chunk));
+  }
+  return Buffer.concat(chunks).toString("utf-8");
+}
+
+function sameOrigin(url: string, other: string): boolean {
+  try {
+    return new URL(url).origin === new URL(other).origin;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Headers for downloading a protected spec: the spec-specific headers, plus the configured credentials
+ * when the spec is served by the API itself or specCredentials allows sending them elsewhere.
+ * Returns undefined when nothing is to be sent so public specs are fetched without extra headers.
+ */
+export function specRequestHeaders(config: Config, specPath?: string): Record<string, string> | undefined {
+  const { location } = resolveSpecSource(specPath);
+  const sendCredentials =
+    Boolean(config.apiKey || config.basicAuth) &&
+    (config.specCredentials || sameOrigin(location, config.defaultBaseUrl ?? DEFAULT_BASE_URL));
+  if (!sendCredentials && !config.specHeaders) {
+    return undefined;
+  }
+  const { "Content-Type": _, ...headers } = getDefaultHeaders({
+    headers: config.specHeaders,
+    ...(sendCredentials ? { apiKey: config.apiKey, basicAuth: config.basicAuth } : {}),
+  });
+  return headers;
+}
+
+/**
+ * Options for downloading the spec at specPath as configured: the headers to send and how often to retry
+ */
+export function specLoadOptions(config: Config, specPath?: string): LoadSpecOptions {
+  return { headers: specRequestHeaders(config, specPath), maxRetries: config.maxRetries };
+}
+
+async function fetchSpecWithRetry(
+  url: string,
+  maxRetries: number,
+  retryDelayMs: number,
+  headers?: Record<string, string>,
+) {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return headers ? await axios.get(url, { headers }) : await axios.get(url);
+    } catch (error: any) {
+      if (attempt >= maxRetries || !RETRYABLE_NETWORK_ERRORS.includes(error.code)) {
+        throw error;
+      }
+      const delay = retryDelayMs * 2 ** attempt;
+      console.error(`Failed to download OpenAPI specification (${error.code}), retrying in ${delay}ms...`);
+      await new Promise((resolve) => setTimeout(resolve, delay));
+    }
+  }
+}
+
+const DEFAULT_SPEC_URL = "http://localhost:31009/docs/openapi.json";
+
+/**
+ * Where a spec is loaded from: stdin, a local file, a URL given explicitly, or the spec served by the
+ * local Anytype app when no spec path is given
+ */
+export type SpecSource = {
+  kind: "stdin" | "file" | "url" | "default-url";
+  location: string;
+};
+
+export function resolveSpecSource(specPath?: string): SpecSource {
+  if (!specPath) {
+    return { kind: "default-url", location: DEFAULT_SPEC_URL };
+  }
+  if (specPath === STDIN_SPEC_PATH) {
+    return { kind: "stdin", location: STDIN_SPEC_PATH };
+  }
+  if (specPath.startsWith("http://") || specPath.startsWith("https://")) {
+    return { kind: "url", location: specPath };
+  }
+  return { kind: "file", location: path.resolve(process.cwd(), specPath) };
+}
+
+export async function loadOpenApiSpec(
+  specPath?: string,
+  { maxRetries = 3, retryDelayMs = 500, headers, stdin = process.stdin }: LoadSpecOptions = {},
+): Promise<OpenAPIV3.Document> {
+  const source = resolveSpecSource(specPath);
+  const finalSpec = source.location;
+  console.error(`Loading OpenAPI specification from ${source.kind} ${source.location}`);
   let rawSpec: string;
 
-  if (finalSpec.startsWith("http://") || finalSpec.startsWith("https://")) {
+  if (source.kind === "stdin") {
+    rawSpec = await readStream(stdin);
+  } else if (source.kind === "url" || source.kind === "default-url") {
     try {
-      const response = await axios.get(finalSpec);
+      const response = await fetchSpecWithRetry(finalSpec, maxRetries, retryDelayMs, headers);
       rawSpec = typeof response.data === "string" ? response.data : JSON.stringify(response.data);
     } catch (error: any) {
       if (error.code === "ECONNREFUSED") {
         console.error("Can't connect to API. Please ensure Anytype is running and reachable.");
         process.exit(1);
       }
+      if (RETRYABLE_NETWORK_ERRORS.includes(error.code)) {
+        console.error(
+          `Network failure while downloading OpenAPI specification after ${maxRetries + 1} attempts:`,
+          error.message,
+        );
+        process.exit(1);
+      }
       console.error("Failed to fetch OpenAPI specification from URL:", error.message);
       process.exit(1);
     }
   } else {
-    const filePath = path.resolve(process.cwd(), finalSpec);
-    rawSpec = fs.readFileSync(filePath, "utf-8");
+    rawSpec = fs.readFileSync(finalSpec, "utf-8");
   }
 
   try {
-    return JSON.parse(rawSpec) as OpenAPIV3.Document;
+    const openApiSpec = JSON.parse(rawSpec) as OpenAPIV3.Document;
+    const errors = validateOpenApiSpec(openApiSpec);
+    if (errors.length > 0) {
+      throw new ValidationError(errors);
+    }
+    return openApiSpec;
   } catch (error: any) {
+    if (error instanceof ValidationError) {
+      throw error;
+    }
     console.error("Failed to parse OpenAPI specification:", error.message);
     process.exit(1);
   }
 }
 
-export async function initProxy(specPath: string) {
-  const openApiSpec = await loadOpenApiSpec(specPath);
-  const proxy = new MCPProxy("Anytype API", openApiSpec);
+/**
+ * Start the server over stdio. Overrides, e.g. from command line flags, take precedence over the environment.
+ */
+export async function initProxy(specPath: string, overrides: Partial<Config> = {}) {
+  // The server speaks MCP over stdin, so the spec cannot be piped in through it as well
+  if (specPath === STDIN_SPEC_PATH) {
+    throw new Error(
+      "Cannot read the spec from stdin when running the server, which serves MCP over stdin. " +
+        "Reading from - works for validate, list-tools, lint-spec and diff-spec",
+    );
+  }
+  const config = { ...loadConfig(), ...overrides };
+  const openApiSpec = await loadOpenApiSpec(specPath, specLoadOptions(config, specPath));
+  const proxy = new MCPProxy("Anytype API", openApiSpec, config, resolveSpecSource(specPath));
+
+  if (config.warmupOnStart) {
+    await proxy.warmup();
+  }
+
+  process.on("SIGHUP", () => {
+    console.error("Received SIGHUP, clearing the response cache");
+    proxy.clearCache();
+  });
 
   console.error("Connecting to Anytype API...");
   return proxy.connect(new StdioServerTransport());
@@ -0,0 +1,71 @@
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+const LOG_LEVELS: Record<LogLevel, number> = {
+  error: 0,
+  warn: 1,
+  info: 2,
+  debug: 3,
+  trace: 4,
+};
+
+// Keys whose values are masked before a body is written to the log
+const SENSITIVE_KEYS = ["authorization", "api_key", "app_key", "cookie", "password", "secret", "token"];
+
+const MAX_LOGGED_BODY_CHARS = 4096;
+
+/**
+ * Resolve the active log level from the OPENAPI_MCP_LOG_LEVEL environment variable.
+ * Defaults to "info" when unset or unknown.
+ */
+export function getLogLevel(): LogLevel {
+  const level = process.env.OPENAPI_MCP_LOG_LEVEL?.toLowerCase();
+  return level && level in LOG_LEVELS ? (level as LogLevel) : "info";
+}
+
+export function isLevelEnabled(level: LogLevel): boolean {
+  return LOG_LEVELS[level] <= LOG_LEVELS[getLogLevel()];
+}
+
+/**
+ * Return a copy of the value with sensitive fields masked
+ */
+export function redact(value: unknown): unknown {
+  if (Array.isArray(value)) {
+    return value.map(redact);
+  }
+  if (value && typeof value === "object") {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, v]) => [
+        key,
+        SENSITIVE_KEYS.some((s) => key.toLowerCase().includes(s)) ? "[redacted]" : redact(v),
+      ]),
+    );
+  }
+  return value;
+}
+
+/**
+ * Serialize a request or response body for logging, redacting sensitive fields
+ * and capping the output size.
+ */
+export function formatBodyForLog(body: unknown, maxChars: number = MAX_LOGGED_BODY_CHARS): string {
+  if (body === undefined || body === null) {
+    return "<empty>";
+  }
+  if (Buffer.isBuffer(body)) {
+    return `<${body.length} bytes>`;
+  }
+  const text = typeof body === "string" ? body : JSON.stringify(redact(body));
+  if (text.length <= maxChars) {
+    return text;
+  }
+  return `${text.slice(0, maxChars)}...[truncated ${text.length - maxChars} chars]`;
+}
+
+/**
+ * Log a body at trace level. Does nothing unless OPENAPI_MCP_LOG_LEVEL=trace.
+ */
+export function traceBody(message: string, body: unknown) {
+  if (!isLevelEnabled("trace")) return;
+  console.error(message, formatBodyForLog(body));
+}
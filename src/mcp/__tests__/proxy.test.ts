@@ -1,9 +1,12 @@
+import { Server } from "@modelcontextprotocol/sdk/server/index.js";
 import type { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
+import { ErrorCode, McpError } from "@modelcontextprotocol/sdk/types.js";
 import { Headers } from "node-fetch";
 import { OpenAPIV3 } from "openapi-types";
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
-import { HttpClient } from "../../client/http-client";
-import { MCPProxy } from "../proxy";
+import { HttpClient, HttpClientError } from "../../client/http-client";
+import { ConfigError, loadConfig } from "../../config";
+import { MCPProxy, SERVER_BUSY, SERVER_INFO_TOOL, serverBaseUrl } from "../proxy";
 
 // Mock the dependencies
 vi.mock("../../client/http-client");
@@ -79,6 +82,229 @@ describe("MCPProxy", () => {
     });
   });
 
+  describe("output schemas", () => {
+    beforeEach(() => {
+      mockOpenApiSpec.paths["/spaces/{spaceId}"] = {
+        get: {
+          operationId: "getSpace",
+          parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+          responses: {
+            "200": {
+              description: "The space",
+              content: {
+                "application/json": {
+                  schema: { type: "object", properties: { id: { type: "string" }, name: { type: "string" } } },
+                },
+              },
+            },
+          },
+        },
+      };
+    });
+
+    it("should list the response schema as the outputSchema when enabled", () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { outputSchemas: true });
+
+      const outputSchema = proxy.getTool("API-getSpace")?.outputSchema;
+      expect(outputSchema).toMatchObject({
+        type: "object",
+        properties: { id: { type: "string" }, name: { type: "string" } },
+        description: "The space",
+      });
+      expect(outputSchema).not.toHaveProperty("$defs");
+      expect(proxy.getTool("API-getTest")).not.toHaveProperty("outputSchema");
+      expect(new MCPProxy("test-proxy", mockOpenApiSpec).getTool("API-getSpace")).not.toHaveProperty("outputSchema");
+    });
+
+    it("should return the response as structuredContent for tools with an outputSchema", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { outputSchemas: true });
+      const data = { id: "space1", name: "Notes" };
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+        data,
+        status: 200,
+        headers: new Headers({ "content-type": "application/json" }),
+      });
+
+      const result = await proxy.callTool("API-getSpace", { spaceId: "space1" });
+
+      expect(result.structuredContent).toEqual(data);
+      expect(JSON.parse((result.content[0] as { text: string }).text)).toEqual(data);
+    });
+
+    it("should not return structured content when the result differs from the response body", async () => {
+      const data = { id: "space1", name: "Notes" };
+      const respond = (status: number, headers: Record<string, string>) =>
+        (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValueOnce({
+          data,
+          status,
+          headers: new Headers({ "content-type": "application/json", ...headers }),
+        });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, {
+        outputSchemas: true,
+        extractResponseHeaders: ["Location"],
+      });
+
+      respond(200, { location: "/spaces/space1" });
+      const withHeaders = await proxy.callTool("API-getSpace", { spaceId: "space1" });
+      respond(206, { "content-range": "bytes 0-9/100" });
+      const partial = await proxy.callTool("API-getSpace", { spaceId: "space1" });
+
+      expect(withHeaders).not.toHaveProperty("structuredContent");
+      expect(partial).not.toHaveProperty("structuredContent");
+    });
+  });
+
+  describe("server info tool", () => {
+    it("should return the spec info block and tool count without calling the API", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { exposeServerInfoTool: true });
+
+      const result = await proxy.callTool(SERVER_INFO_TOOL);
+
+      expect(JSON.parse((result.content[0] as { text: string }).text)).toEqual({
+        info: { title: "Test API", version: "1.0.0" },
+        toolCount: 1,
+      });
+      expect(proxy.listTools().map((tool) => tool.name)).toEqual(["API-getTest", SERVER_INFO_TOOL]);
+      expect(HttpClient.prototype.executeOperation).not.toHaveBeenCalled();
+    });
+
+    it("should not list the tool unless enabled", () => {
+      expect(proxy.getTool(SERVER_INFO_TOOL)).toBeUndefined();
+    });
+  });
+
+  describe("getToolSchema", () => {
+    it("should return the converted input schema of a known tool", () => {
+      mockOpenApiSpec.paths = {
+        "/pets/{petId}": {
+          get: {
+            operationId: "getPet",
+            parameters: [
+              { name: "petId", in: "path", required: true, schema: { type: "string" } },
+              { name: "include", in: "query", schema: { type: "string" } },
+            ],
+            responses: { "200": { description: "Success" } },
+          },
+        },
+      };
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec);
+
+      const schema = proxy.getToolSchema("API-getPet");
+
+      expect(schema?.properties).toHaveProperty("petId");
+      expect(schema?.properties).toHaveProperty("include");
+      expect(schema?.required).toEqual(["petId"]);
+      expect(proxy.getTool("API-getPet")?.name).toBe("API-getPet");
+    });
+
+    it("should return undefined for an unknown tool", () => {
+      expect(proxy.getToolSchema("API-unknown")).toBeUndefined();
+      expect(proxy.getTool("API-unknown")).toBeUndefined();
+    });
+
+    it("should look tools up without building the tool list again", async () => {
+      const buildSpy = vi.spyOn(proxy as any, "buildToolList");
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+
+      proxy.getToolSchema("API-getTest");
+      await proxy.callTool("API-getTest", {});
+
+      expect(buildSpy).not.toHaveBeenCalled();
+    });
+  });
+
+  describe("toolsByMethod", () => {
+    it("should return only the tools for the given method, ignoring case", () => {
+      mockOpenApiSpec.paths["/pets"] = {
+        get: { operationId: "listPets", responses: { "200": { description: "Success" } } },
+        post: { operationId: "createPet", responses: { "201": { description: "Created" } } },
+      };
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec);
+
+      expect(proxy.toolsByMethod("GET").map((tool) => tool.name)).toEqual(["API-getTest", "API-listPets"]);
+      expect(proxy.toolsByMethod("post").map((tool) => tool.name)).toEqual(["API-createPet"]);
+      expect(proxy.toolsByMethod("PATCH")).toEqual([]);
+    });
+  });
+
+  describe("resolveOperationId", () => {
+    it("should resolve a prefixed and sanitized tool name to its original operationId", () => {
+      const operationId = `get_${"object_".repeat(10)}details`;
+      mockOpenApiSpec.paths["/objects"] = {
+        get: { operationId, responses: { "200": { description: "Success" } } },
+      };
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec);
+
+      const toolName = proxy.listTools().find((tool) => tool.name !== "API-getTest")!.name;
+
+      expect(toolName).not.toContain("_");
+      expect(proxy.resolveOperationId(toolName)).toBe(operationId);
+      expect(proxy.resolveOperationId("API-getTest")).toBe("getTest");
+      expect(proxy.resolveOperationId("API-unknown")).toBeUndefined();
+    });
+  });
+
+  describe("getServerInfo", () => {
+    it("should report the number and names of tools from the spec", () => {
+      mockOpenApiSpec.paths["/pets"] = {
+        get: { operationId: "listPets", responses: { "200": { description: "Success" } } },
+        post: { operationId: "createPet", responses: { "201": { description: "Created" } } },
+      };
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec);
+
+      const info = proxy.getServerInfo();
+
+      expect(info.name).toBe("test-proxy");
+      expect(info.mode).toBe("stdio");
+      expect(info.toolCount).toBe(3);
+      expect(info.toolNames).toEqual(["API-getTest", "API-listPets", "API-createPet"]);
+      expect(JSON.parse(JSON.stringify(info))).toEqual(info);
+    });
+  });
+
+  describe("instructions", () => {
+    function serverOptions() {
+      return vi.mocked(Server).mock.lastCall?.[1];
+    }
+
+    it("should warn clients when no API key is configured", () => {
+      new MCPProxy("test-proxy", mockOpenApiSpec, {});
+
+      expect(serverOptions()?.instructions).toContain("No API key is configured");
+    });
+
+    it("should refuse to start without an API key when one is required", () => {
+      expect(() => new MCPProxy("test-proxy", mockOpenApiSpec, { requireApiKey: true })).toThrow(ConfigError);
+      expect(() => new MCPProxy("test-proxy", mockOpenApiSpec, { requireApiKey: true })).toThrow(
+        "OPENAPI_MCP_REQUIRE_API_KEY is set, but no API key is configured",
+      );
+      const withKey = () => new MCPProxy("test-proxy", mockOpenApiSpec, { requireApiKey: true, apiKey: "secret" });
+      expect(withKey).not.toThrow();
+    });
+
+    it("should warn when the configured Anytype-Version differs from the spec version", () => {
+      const warnSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+      const config = { apiKey: "secret", headers: { "anytype-version": "2025-11-08" } };
+
+      new MCPProxy("test-proxy", mockOpenApiSpec, config);
+
+      expect(warnSpy).toHaveBeenCalledWith(
+        expect.stringContaining("The spec is for API version 1.0.0, but Anytype-Version 2025-11-08 is configured"),
+      );
+      expect(serverOptions()?.instructions).toContain("Anytype-Version 2025-11-08");
+      expect(() => new MCPProxy("test-proxy", mockOpenApiSpec, { ...config, strictApiVersion: true })).toThrow(
+        ConfigError,
+      );
+      warnSpy.mockRestore();
+    });
+
+    it("should leave out instructions when nothing is degraded", () => {
+      new MCPProxy("test-proxy", mockOpenApiSpec, { apiKey: "secret" });
+
+      expect(serverOptions()).toEqual({ capabilities: { tools: {} } });
+    });
+  });
+
   describe("callTool handler", () => {
     it("should execute operation and return formatted response", async () => {
       // Mock HttpClient response
@@ -124,6 +350,55 @@ describe("MCPProxy", () => {
       });
     });
 
+    it("should return the same result when calling a tool by its operationId", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+        data: { message: "success" },
+        status: 200,
+      });
+
+      const byName = await proxy.callTool("API-getTest", { limit: 1 });
+      const byOperationId = await proxy.callByOperationId("getTest", { limit: 1 });
+
+      expect(byOperationId).toEqual(byName);
+      expect(HttpClient.prototype.executeOperation).toHaveBeenLastCalledWith(
+        expect.objectContaining({ operationId: "getTest" }),
+        { limit: 1 },
+      );
+      await expect(proxy.callByOperationId("unknownOperation")).rejects.toThrow("Operation unknownOperation not found");
+    });
+
+    it("should return the raw response data when invoking a tool", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+        data: { id: 1, name: "Test" },
+        status: 200,
+      });
+
+      const result = await proxy.invoke("API-getTest", { limit: 1 });
+
+      expect(result).toEqual({ id: 1, name: "Test" });
+      expect(HttpClient.prototype.executeOperation).toHaveBeenLastCalledWith(
+        expect.objectContaining({ operationId: "getTest" }),
+        { limit: 1 },
+      );
+      await expect(proxy.invoke("API-unknown")).rejects.toThrow("Method API-unknown not found");
+    });
+
+    it("should fill in omitted arguments from the configured defaults", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+      const config = loadConfig({
+        OPENAPI_MCP_ARG_DEFAULTS: JSON.stringify({ "API-getTest": { space_id: "${SPACE_ID}", limit: "10" } }),
+        SPACE_ID: "space-1",
+      });
+      const proxyWithDefaults = new MCPProxy("test-proxy", mockOpenApiSpec, config);
+
+      await proxyWithDefaults.callTool("API-getTest", { limit: 5 });
+
+      expect(HttpClient.prototype.executeOperation).toHaveBeenLastCalledWith(
+        expect.objectContaining({ operationId: "getTest" }),
+        { limit: 5, space_id: "space-1" },
+      );
+    });
+
     it("should throw error for non-existent operation", async () => {
       const server = (proxy as any).server;
       const handlers = server.setRequestHandler.mock.calls
@@ -141,6 +416,128 @@ describe("MCPProxy", () => {
       ).rejects.toThrow("Method nonExistentMethod not found");
     });
 
+    it("should reject arguments that are not a JSON object as invalid params", async () => {
+      const callToolHandler = (proxy as any).server.setRequestHandler.mock.calls.at(-1)[1];
+
+      const call = callToolHandler({ params: { name: "API-getTest", arguments: ["space-1"] } });
+
+      await expect(call).rejects.toThrow(McpError);
+      await expect(call).rejects.toMatchObject({
+        code: ErrorCode.InvalidParams,
+        message: expect.stringContaining("Arguments of API-getTest must be a JSON object, got an array"),
+      });
+      expect(HttpClient.prototype.executeOperation).not.toHaveBeenCalled();
+    });
+
+    it("should name tools in snake case and call them under that name", async () => {
+      mockOpenApiSpec.paths["/users/{id}"] = {
+        get: { operationId: "getUserById", responses: { "200": { description: "Success" } } },
+      };
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { nameCase: "snake" });
+
+      await proxy.callTool("API-get_user_by_id", { id: "u1" });
+
+      expect(proxy.listTools().map((tool) => tool.name)).toEqual(["API-get_test", "API-get_user_by_id"]);
+      expect(HttpClient.prototype.executeOperation).toHaveBeenCalledWith(
+        expect.objectContaining({ operationId: "getUserById" }),
+        { id: "u1" },
+      );
+    });
+
+    it("should return a failed response as a result carrying the status code when configured", async () => {
+      vi.spyOn(console, "error").mockImplementation(() => {});
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { returnErrorsAsResults: true });
+      const body = { object: "error", code: "not_found", message: "Space not found" };
+      // The mocked HttpClientError constructor doesn't set its fields
+      const error = Object.assign(new HttpClientError("Not Found", 404, body), { status: 404, data: body });
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockRejectedValueOnce(error);
+
+      const result = await proxy.callTool("API-getTest", {});
+
+      expect(result.isError).toBeUndefined();
+      expect(JSON.parse((result.content[0] as { text: string }).text)).toEqual({ status: 404, body });
+    });
+
+    it("should return a markdown response as text with its media type", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValueOnce({
+        data: "# Notes\n\n- first item",
+        status: 200,
+        headers: new Headers({ "content-type": "text/markdown; charset=utf-8" }),
+      });
+
+      const result = await proxy.callTool("API-getTest", {});
+
+      expect(result.content).toEqual([{ type: "text", text: "# Notes\n\n- first item", mimeType: "text/markdown" }]);
+    });
+
+    it("should return other non-JSON responses as an embedded resource", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValueOnce({
+        data: "<object id=\"o1\"/>",
+        status: 200,
+        headers: new Headers({ "content-type": "application/xml" }),
+      });
+
+      const result = await proxy.callTool("API-getTest", {});
+
+      expect(result.content).toEqual([
+        {
+          type: "resource",
+          resource: {
+            uri: "anytype-mcp://tools/API-getTest/result",
+            mimeType: "application/xml",
+            text: '<object id="o1"/>',
+          },
+        },
+      ]);
+    });
+
+    it("should return binary responses base64 encoded as an image or a blob", async () => {
+      const bytes = Buffer.from([0x25, 0x50, 0x44, 0x46, 0x00, 0xff]);
+      const respond = (contentType: string) => ({
+        data: bytes,
+        status: 200,
+        headers: new Headers({ "content-type": contentType }),
+      });
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>)
+        .mockResolvedValueOnce(respond("image/png"))
+        .mockResolvedValueOnce(respond("application/pdf"));
+
+      const image = await proxy.callTool("API-getTest", {});
+      const pdf = await proxy.callTool("API-getTest", {});
+
+      expect(image.content).toEqual([{ type: "image", data: bytes.toString("base64"), mimeType: "image/png" }]);
+      expect(pdf.content).toEqual([
+        {
+          type: "resource",
+          resource: {
+            uri: "anytype-mcp://tools/API-getTest/result",
+            mimeType: "application/pdf",
+            blob: bytes.toString("base64"),
+          },
+        },
+      ]);
+    });
+
+    it("should turn an unexpected failure into an error result and keep serving", async () => {
+      vi.spyOn(console, "error").mockImplementation(() => {});
+      const callToolHandler = (proxy as any).server.setRequestHandler.mock.calls.at(-1)[1];
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>)
+        .mockRejectedValueOnce(new TypeError("Cannot read properties of undefined (reading 'id')"))
+        .mockResolvedValueOnce({ data: { ok: true }, status: 200 });
+
+      const failed = await callToolHandler({ params: { name: "API-getTest", arguments: {} } });
+      const next = await callToolHandler({ params: { name: "API-getTest", arguments: {} } });
+
+      expect(failed).toEqual({
+        content: [
+          { type: "text", text: "Tool API-getTest failed: Cannot read properties of undefined (reading 'id')" },
+        ],
+        isError: true,
+      });
+      expect(next.content[0].text).toBe('{"ok":true}');
+    });
+
     it("should handle tool names exceeding 64 characters", async () => {
       // Mock HttpClient response
       const mockResponse = {
@@ -186,6 +583,175 @@ describe("MCPProxy", () => {
         ],
       });
     });
+
+    it("should reject a request larger than the configured maximum size", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { maxRequestBytes: 100 });
+
+      const server = (proxy as any).server;
+      const callToolHandler = server.setRequestHandler.mock.calls.at(-1)[1];
+      const error = await callToolHandler({
+        params: { name: "API-getTest", arguments: { text: "x".repeat(200) } },
+      }).catch((e: unknown) => e);
+
+      expect(error).toBeInstanceOf(McpError);
+      expect(error.code).toBe(ErrorCode.InvalidRequest);
+      expect(error.message).toMatch(/exceeds the limit of 100/);
+      expect(HttpClient.prototype.executeOperation).not.toHaveBeenCalled();
+    });
+
+    it("should reject a string argument that does not match its declared pattern before calling the API", async () => {
+      mockOpenApiSpec.paths["/tags/{slug}"] = {
+        get: {
+          operationId: "getTag",
+          parameters: [{ name: "slug", in: "path", required: true, schema: { type: "string", pattern: "^[a-z]+$" } }],
+          responses: { "200": { description: "Success" } },
+        },
+      };
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec);
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+
+      const error = await proxy.callTool("API-getTag", { slug: "Work-2" }).catch((e: unknown) => e);
+      await proxy.callTool("API-getTag", { slug: "work" });
+
+      expect(error).toBeInstanceOf(McpError);
+      expect((error as McpError).code).toBe(ErrorCode.InvalidParams);
+      expect((error as McpError).message).toContain('Argument slug of API-getTag is "Work-2"');
+      expect((error as McpError).message).toContain("^[a-z]+$");
+      expect(HttpClient.prototype.executeOperation).toHaveBeenCalledTimes(1);
+    });
+
+    it("should reject calls beyond the queue depth until one finishes", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { requestQueueDepth: 1 });
+      const callToolHandler = (proxy as any).server.setRequestHandler.mock.calls.at(-1)[1];
+      let finishSlowCall: (value: unknown) => void = () => {};
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>)
+        .mockReturnValueOnce(new Promise((resolve) => (finishSlowCall = resolve)))
+        .mockResolvedValue({ data: {}, status: 200 });
+      const request = { params: { name: "API-getTest", arguments: {} } };
+
+      const slowCall = callToolHandler(request);
+      const error = await callToolHandler(request).catch((e: unknown) => e);
+      finishSlowCall({ data: { slow: true }, status: 200 });
+      await slowCall;
+
+      expect(error).toBeInstanceOf(McpError);
+      expect(error.code).toBe(SERVER_BUSY);
+      expect(HttpClient.prototype.executeOperation).toHaveBeenCalledTimes(1);
+      await expect(callToolHandler(request)).resolves.toHaveProperty("content");
+    });
+
+    it("should log the call duration and report it in the result metadata when enabled", async () => {
+      const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { includeResponseMetadata: true });
+
+      const server = (proxy as any).server;
+      const callToolHandler = server.setRequestHandler.mock.calls.at(-1)[1];
+      const result = await callToolHandler({ params: { name: "API-getTest", arguments: {} } });
+
+      expect(consoleSpy).toHaveBeenCalledWith(expect.stringMatching(/^Tool API-getTest finished in \d+ms$/));
+      expect(result._meta.duration_ms).toEqual(expect.any(Number));
+    });
+
+    it("should return partial content together with its content range", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+        data: "first hundred bytes",
+        status: 206,
+        headers: new Headers({ "content-range": "bytes 0-99/1000" }),
+      });
+
+      const server = (proxy as any).server;
+      const callToolHandler = server.setRequestHandler.mock.calls.at(-1)[1];
+      const result = await callToolHandler({ params: { name: "API-getTest", arguments: { _range: "bytes=0-99" } } });
+
+      expect(JSON.parse(result.content[0].text)).toEqual({
+        content_range: "bytes 0-99/1000",
+        data: "first hundred bytes",
+      });
+    });
+
+    it("should indent results only when pretty results are enabled", async () => {
+      const data = { space: { id: "s1", name: "Work" } };
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data, status: 200 });
+      const callTool = async (prettyResults: boolean) => {
+        proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { prettyResults });
+        const handler = (proxy as any).server.setRequestHandler.mock.calls.at(-1)[1];
+        const result = await handler({ params: { name: "API-getTest", arguments: {} } });
+        return result.content[0].text as string;
+      };
+
+      const pretty = await callTool(true);
+      const compact = await callTool(false);
+
+      expect(pretty).toBe(JSON.stringify(data, null, 2));
+      expect(compact).toBe(JSON.stringify(data));
+      expect(compact).not.toMatch(/\n|  /);
+      expect(JSON.parse(pretty)).toEqual(JSON.parse(compact));
+    });
+
+    it("should unwrap results that only hold the configured key", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { unwrapResponseKey: "data" });
+      const invokeWith = async (data: unknown) => {
+        (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data, status: 200 });
+        return proxy.invoke("API-getTest", {});
+      };
+
+      expect(await invokeWith({ data: { id: "o1", name: "Note" } })).toEqual({ id: "o1", name: "Note" });
+      expect(await invokeWith({ data: [], pagination: { total: 0 } })).toEqual({ data: [], pagination: { total: 0 } });
+    });
+
+    it("should return configured response headers along with the body", async () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { extractResponseHeaders: ["Location", "X-Object-Id"] });
+      const invokeWith = async (headers: Record<string, string>) => {
+        (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+          data: { ok: true },
+          status: 201,
+          headers: new Headers(headers),
+        });
+        return proxy.invoke("API-getTest", {});
+      };
+
+      expect(await invokeWith({ location: "/objects/o1", "content-type": "application/json" })).toEqual({
+        _headers: { Location: "/objects/o1" },
+        body: { ok: true },
+      });
+      expect(await invokeWith({ "content-type": "application/json" })).toEqual({ ok: true });
+    });
+
+    it("should return array results as one JSON line per item in NDJSON mode", async () => {
+      const data = [{ id: "o1" }, { id: "o2" }, { id: "o3" }];
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data, status: 200 });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { resultFormat: "ndjson" });
+
+      const arrayResult = await proxy.callTool("API-getTest", {});
+      const lines = (arrayResult.content[0] as { text: string }).text.split("\n");
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({
+        data: { total: 3 },
+        status: 200,
+      });
+      const objectResult = await proxy.callTool("API-getTest", {});
+
+      expect(lines).toHaveLength(3);
+      expect(lines.map((line) => JSON.parse(line))).toEqual(data);
+      expect(objectResult.content[0]).toEqual({ type: "text", text: '{"total":3}' });
+    });
+
+    it("should truncate results larger than the configured maximum", async () => {
+      const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+      const data = { items: "x".repeat(100) };
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data, status: 200 });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, { maxResultChars: 20 });
+
+      const server = (proxy as any).server;
+      const callToolHandler = server.setRequestHandler.mock.calls.at(-1)[1];
+      const result = await callToolHandler({ params: { name: "API-getTest", arguments: {} } });
+
+      const fullText = JSON.stringify(data);
+      expect(result.content[0].text).toBe(`${fullText.slice(0, 20)}...[truncated ${fullText.length - 20} chars]`);
+      expect(consoleSpy).toHaveBeenCalledWith(
+        `Tool result of ${fullText.length} chars exceeds the limit of 20, truncating it`,
+      );
+    });
   });
 
   describe("getContentType", () => {
@@ -200,6 +766,73 @@ describe("MCPProxy", () => {
     });
   });
 
+  describe("tool overrides", () => {
+    it("should change the method, path and description of a tool", async () => {
+      (HttpClient.prototype.executeOperation as ReturnType<typeof vi.fn>).mockResolvedValue({ data: {}, status: 200 });
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, {
+        toolOverrides: { "API-getTest": { method: "POST", path: "/test/search", description: "Search tests" } },
+      });
+
+      const server = (proxy as any).server;
+      const callToolHandler = server.setRequestHandler.mock.calls.at(-1)[1];
+      await callToolHandler({ params: { name: "API-getTest", arguments: {} } });
+
+      expect(HttpClient.prototype.executeOperation).toHaveBeenCalledWith(
+        expect.objectContaining({ operationId: "getTest", method: "post", path: "/test/search" }),
+        {},
+      );
+      expect(proxy.getTool("API-getTest")?.description).toBe("Search tests");
+    });
+
+    it("should replace descriptions from the spec with configured ones", () => {
+      proxy = new MCPProxy("test-proxy", mockOpenApiSpec, {
+        toolDescriptions: { "API-getTest": "List test records, newest first" },
+        appendRouteToDescription: true,
+      });
+
+      const tool = proxy.listTools().find((candidate) => candidate.name === "API-getTest");
+
+      expect(tool?.description).toBe("List test records, newest first\n\nGET /test");
+    });
+  });
+
+  describe("base URL", () => {
+    it("should substitute server variables from the configuration or their defaults", () => {
+      mockOpenApiSpec.servers = [
+        {
+          url: "https://{region}.anytype.io:{port}/v1",
+          variables: { region: { default: "eu" }, port: { default: "443" } },
+        },
+      ];
+
+      expect(serverBaseUrl(mockOpenApiSpec)).toBe("https://eu.anytype.io:443/v1");
+      expect(serverBaseUrl(mockOpenApiSpec, { region: "us" })).toBe("https://us.anytype.io:443/v1");
+
+      new MCPProxy("test-proxy", mockOpenApiSpec, { serverVariables: { port: "8443" } });
+      expect(HttpClient).toHaveBeenLastCalledWith(
+        expect.objectContaining({ baseUrl: "https://eu.anytype.io:8443/v1" }),
+        expect.anything(),
+      );
+    });
+
+    it("should fall back to the configured default when the spec declares no servers", () => {
+      vi.spyOn(console, "error").mockImplementation(() => {});
+      delete mockOpenApiSpec.servers;
+
+      new MCPProxy("test-proxy", mockOpenApiSpec, { defaultBaseUrl: "https://anytype.example.com" });
+      expect(HttpClient).toHaveBeenLastCalledWith(
+        expect.objectContaining({ baseUrl: "https://anytype.example.com" }),
+        expect.anything(),
+      );
+
+      new MCPProxy("test-proxy", mockOpenApiSpec, {});
+      expect(HttpClient).toHaveBeenLastCalledWith(
+        expect.objectContaining({ baseUrl: "http://localhost:31009" }),
+        expect.anything(),
+      );
+    });
+  });
+
   describe("parseHeadersFromEnv", () => {
     const originalEnv = process.env;
 
@@ -1,11 +1,23 @@
 import { Server } from "@modelcontextprotocol/sdk/server/index.js";
 import { Transport } from "@modelcontextprotocol/sdk/shared/transport.js";
-import { CallToolRequestSchema, ListToolsRequestSchema, Tool } from "@modelcontextprotocol/sdk/types.js";
+import {
+  CallToolRequestSchema,
+  CallToolResult,
+  ErrorCode,
+  ListToolsRequestSchema,
+  McpError,
+  Tool,
+} from "@modelcontextprotocol/sdk/types.js";
 import { JSONSchema7 as IJsonSchema } from "json-schema";
 import { Headers } from "node-fetch";
 import { OpenAPIV3 } from "openapi-types";
-import { HttpClient, HttpClientError } from "../client/http-client";
-import { OpenAPIToMCPConverter } from "../openapi/parser";
+import packageJson from "../../package.json";
+import { HttpClient, HttpClientError, HttpClientResponse } from "../client/http-client";
+import { Config, ConfigError, DEFAULT_BASE_URL, loadConfig, ToolOverride } from "../config";
+import type { SpecSource } from "../init-server";
+import { isLevelEnabled } from "../logger";
+import { SkippedOperation } from "../openapi/parser";
+import { convertWithCache } from "../openapi/tool-cache";
 
 type PathItemObject = OpenAPIV3.PathItemObject & {
   get?: OpenAPIV3.OperationObject;
@@ -18,113 +30,625 @@ type PathItemObject = OpenAPIV3.PathItemObject & {
 type NewToolDefinition = {
   methods: Array<{
     name: string;
+    operationId: string;
     description: string;
     inputSchema: IJsonSchema & { type: "object" };
     outputSchema?: IJsonSchema;
   }>;
 };
 
+export type ServerInfo = {
+  name: string;
+  version: string;
+  mode: "stdio";
+  toolCount: number;
+  toolNames: string[];
+  skippedOperations: SkippedOperation[];
+  specSource?: SpecSource;
+};
+
+export const SERVER_VERSION = packageJson.version;
+
+// JSON-RPC server error for a call turned away while requestQueueDepth calls are in flight, like an HTTP 503
+export const SERVER_BUSY = -32000;
+
+// Built-in tool describing the API the server exposes, listed when exposeServerInfoTool is set
+export const SERVER_INFO_TOOL = "__server_info";
+
+/**
+ * The URL of the first server the spec declares, with its {variables} replaced by the configured
+ * values or else their declared defaults. Undefined when the spec declares no servers.
+ */
+export function serverBaseUrl(
+  openApiSpec: OpenAPIV3.Document,
+  serverVariables: Record<string, string> = {},
+): string | undefined {
+  const server = openApiSpec.servers?.[0];
+  if (!server?.url) {
+    return undefined;
+  }
+  return server.url.replace(/\{([^}]+)\}/g, (match, name: string) => {
+    const value = serverVariables[name] ?? server.variables?.[name]?.default;
+    if (value === undefined) {
+      console.warn(`Server variable ${name} has no value or default, leaving it in the base URL`);
+      return match;
+    }
+    return String(value);
+  });
+}
+
+// The Anytype API picks its behavior from the Anytype-Version header, which should match the version of the spec
+function apiVersionMismatch(openApiSpec: OpenAPIV3.Document, headers: Record<string, string>): string | undefined {
+  const header = Object.keys(headers).find((key) => key.toLowerCase() === "anytype-version");
+  const configured = header ? headers[header] : undefined;
+  if (!configured || !openApiSpec.info?.version || configured === openApiSpec.info.version) {
+    return undefined;
+  }
+  return `The spec is for API version ${openApiSpec.info.version}, but Anytype-Version ${configured} is configured`;
+}
+
+function formatWarnings(warnings: string[]): string {
+  return `This server is running with reduced functionality:\n${warnings.map((warning) => `- ${warning}`).join("\n")}`;
+}
+
 export class MCPProxy {
+  private name: string;
   private server: Server;
   private httpClient: HttpClient;
   private tools: Record<string, NewToolDefinition>;
   private openApiLookup: Record<string, OpenAPIV3.OperationObject & { method: string; path: string }>;
+  private skippedOperations: SkippedOperation[];
+  // operationId to the name of the tool generated from it
+  private operationIdIndex: Map<string, string>;
+  // The tools as listed to clients, and indexed by name. Built once, after overrides are applied
+  private listedTools: Tool[] = [];
+  private toolsByName = new Map<string, Tool>();
+  private specInfo: OpenAPIV3.InfoObject;
+  private inFlightCalls = 0;
+  private patterns = new Map<string, RegExp | null>();
+  private config: Config;
 
-  constructor(name: string, openApiSpec: OpenAPIV3.Document) {
-    this.server = new Server({ name, version: "1.0.0" }, { capabilities: { tools: {} } });
-    const baseUrl = openApiSpec.servers?.[0].url;
+  constructor(
+    name: string,
+    openApiSpec: OpenAPIV3.Document,
+    config: Config = loadConfig(),
+    private specSource?: SpecSource,
+  ) {
+    this.name = name;
+    this.config = config;
+    this.specInfo = openApiSpec.info;
+    const warnings: string[] = [];
+    let baseUrl = serverBaseUrl(openApiSpec, config.serverVariables);
     if (!baseUrl) {
-      throw new Error("No base URL found in OpenAPI spec");
+      baseUrl = config.defaultBaseUrl ?? DEFAULT_BASE_URL;
+      console.error(`No base URL found in OpenAPI spec, falling back to ${baseUrl}`);
+      warnings.push(`The spec declares no server, so requests go to the fallback ${baseUrl}`);
+    }
+    const headers = { ...config.headers, ...this.parseHeadersFromEnv() };
+    const hasAuthorizationHeader = Object.keys(headers).some((key) => key.toLowerCase() === "authorization");
+    if (!config.apiKey && !config.basicAuth && !config.oauth && !hasAuthorizationHeader) {
+      if (config.requireApiKey) {
+        throw new ConfigError([
+          "OPENAPI_MCP_REQUIRE_API_KEY is set, but no API key is configured through ANYTYPE_API_KEY, " +
+            "OPENAPI_MCP_BASIC_AUTH or an Authorization header",
+        ]);
+      }
+      warnings.push("No API key is configured, so requests to the API are not authenticated");
+    }
+    const versionMismatch = apiVersionMismatch(openApiSpec, headers);
+    if (versionMismatch) {
+      if (config.strictApiVersion) {
+        throw new ConfigError([versionMismatch]);
+      }
+      console.warn(`WARNING: ${versionMismatch}, tool calls may fail or behave unexpectedly`);
+      warnings.push(versionMismatch);
     }
     this.httpClient = new HttpClient(
       {
         baseUrl,
-        headers: this.parseHeadersFromEnv(),
+        headers,
+        apiKey: config.apiKey,
+        apiKeyFile: config.apiKeyFile,
+        basicAuth: config.basicAuth,
+        oauth: config.oauth,
+        acceptLanguage: config.acceptLanguage,
+        etagCache: config.etagCache,
+        http2PriorKnowledge: config.http2PriorKnowledge,
+        minTlsVersion: config.minTlsVersion,
+        healthPath: config.healthPath,
+        caseSensitiveHeaders: config.caseSensitiveHeaders,
+        disableDefaultHeaders: config.disableDefaultHeaders,
+        maxRedirects: config.maxRedirects,
+        offline: config.offline,
+        preferRepresentation: config.preferRepresentation,
+        compressRequest: config.compressRequest,
+        compressRequestMinBytes: config.compressRequestMinBytes,
+        circuitBreakerThreshold: config.circuitBreakerThreshold,
+        circuitBreakerCooldownMs: config.circuitBreakerCooldownMs,
       },
       openApiSpec,
     );
 
     // Convert OpenAPI spec to MCP tools
-    const converter = new OpenAPIToMCPConverter(openApiSpec);
-    const { tools, openApiLookup } = converter.convertToMCPTools();
+    const { tools, openApiLookup, skipped } = convertWithCache(
+      openApiSpec,
+      { useDefs: config.useDefs, mergeAllOf: config.mergeAllOf, nameCase: config.nameCase },
+      config.toolCacheFile,
+    );
     this.tools = tools;
     this.openApiLookup = openApiLookup;
+    this.skippedOperations = skipped;
+    this.operationIdIndex = new Map(
+      Object.entries(openApiLookup).map(([toolName, operation]) => [operation.operationId!, toolName]),
+    );
+    this.applyToolOverrides(this.mergeToolDescriptions(config));
+    if (config.appendRouteToDescription) {
+      this.appendRoutesToDescriptions();
+    }
+    this.listedTools = this.buildToolList();
+    // The first tool listed under a name wins, should truncated names collide
+    this.toolsByName = new Map([...this.listedTools].reverse().map((tool) => [tool.name, tool]));
+    if (skipped.length > 0) {
+      warnings.push(`${skipped.length} operation(s) of the spec could not be converted and have no tool`);
+    }
 
+    // Degraded setups are passed on to clients as instructions, which they show when connecting
+    this.server = new Server(
+      { name, version: SERVER_VERSION },
+      { capabilities: { tools: {} }, ...(warnings.length > 0 ? { instructions: formatWarnings(warnings) } : {}) },
+    );
     this.setupHandlers();
   }
 
+  // toolDescriptions is a shorthand for overriding only descriptions, a description in toolOverrides wins
+  private mergeToolDescriptions(config: Config): Record<string, ToolOverride> {
+    const overrides = { ...config.toolOverrides };
+    for (const [toolName, description] of Object.entries(config.toolDescriptions ?? {})) {
+      overrides[toolName] = { description, ...overrides[toolName] };
+    }
+    return overrides;
+  }
+
+  /**
+   * End each tool description with the HTTP method and path it calls, after any override was applied
+   */
+  private appendRoutesToDescriptions() {
+    for (const [apiName, def] of Object.entries(this.tools)) {
+      for (const method of def.methods) {
+        const operation = this.openApiLookup[`${apiName}-${method.name}`];
+        if (operation) {
+          const route = `${operation.method.toUpperCase()} ${operation.path}`;
+          method.description = method.description ? `${method.description}\n\n${route}` : route;
+        }
+      }
+    }
+  }
+
+  /**
+   * Patch the method, path or description of converted tools, to work around mistakes in the spec
+   */
+  private applyToolOverrides(overrides: Record<string, ToolOverride>) {
+    for (const [toolName, override] of Object.entries(overrides)) {
+      const operation = this.openApiLookup[toolName];
+      if (!operation) {
+        console.warn(`Ignoring override for unknown tool ${toolName}`);
+        continue;
+      }
+      if (override.method) {
+        operation.method = override.method.toLowerCase();
+      }
+      if (override.path) {
+        operation.path = override.path;
+      }
+      if (override.description !== undefined) {
+        for (const [apiName, def] of Object.entries(this.tools)) {
+          def.methods
+            .filter((method) => `${apiName}-${method.name}` === toolName)
+            .forEach((method) => (method.description = override.description!));
+        }
+      }
+    }
+  }
+
   private setupHandlers() {
     // Handle tool listing
     this.server.setRequestHandler(ListToolsRequestSchema, async () => {
-      const tools: Tool[] = [];
-
-      // Add methods as separate tools to match the MCP format
-      Object.entries(this.tools).forEach(([toolName, def]) => {
-        def.methods.forEach((method) => {
-          const toolNameWithMethod = `${toolName}-${method.name}`;
-          const truncatedToolName = this.truncateToolName(toolNameWithMethod);
-          tools.push({
-            name: truncatedToolName,
-            description: method.description,
-            inputSchema: method.inputSchema as Tool["inputSchema"],
-          });
-        });
-      });
-
-      return { tools };
+      return { tools: this.listTools() };
     });
 
     // Handle tool calling
     this.server.setRequestHandler(CallToolRequestSchema, async (request) => {
-      console.error("calling tool", request.params);
+      // Arguments may carry credentials such as cookie values, so only the tool name is logged
+      console.error("calling tool", request.params.name);
+      this.checkRequestSize(request.params);
+      this.acquireCallSlot();
       const { name, arguments: params } = request.params;
-
-      // Find the operation in OpenAPI spec
-      const operation = this.findOperation(name);
-      console.error("operations", this.openApiLookup);
-      if (!operation) {
-        throw new Error(`Method ${name} not found`);
+      try {
+        return await this.callTool(name, params);
+      } catch (error) {
+        // Anything unexpected becomes an error result for this call instead of a failed request
+        if (error instanceof McpError) {
+          throw error;
+        }
+        console.error(`Tool ${name} failed unexpectedly:`, error instanceof Error ? error.message : error);
+        return {
+          content: [{ type: "text", text: `Tool ${name} failed: ${error instanceof Error ? error.message : error}` }],
+          isError: true,
+        };
+      } finally {
+        this.inFlightCalls--;
       }
+    });
+  }
 
-      try {
-        // Execute the operation
-        const response = await this.httpClient.executeOperation(operation, params);
+  /**
+   * Call a tool by the name it is listed under, as the MCP call_tool handler does
+   */
+  async callTool(name: string, params?: Record<string, unknown>): Promise<CallToolResult> {
+    if (this.config.exposeServerInfoTool && name === SERVER_INFO_TOOL) {
+      return { content: [{ type: "text", text: this.serializeResult(this.describeApi()) }] };
+    }
 
-        // Convert response to MCP format
+    // Find the operation in OpenAPI spec
+    const operation = this.findOperation(name);
+    console.error("operations", this.openApiLookup);
+    if (!operation) {
+      throw new McpError(ErrorCode.InvalidParams, `Method ${name} not found`);
+    }
+    // Clients may send any JSON as arguments, which must not silently turn into no arguments at all
+    if (params !== undefined && (typeof params !== "object" || params === null || Array.isArray(params))) {
+      const got = params === null ? "null" : Array.isArray(params) ? "an array" : typeof params;
+      throw new McpError(ErrorCode.InvalidParams, `Arguments of ${name} must be a JSON object, got ${got}`);
+    }
+
+    const args = this.withArgDefaults(name, params);
+    this.checkPatterns(name, args);
+
+    const startedAt = performance.now();
+    try {
+      // Execute the operation
+      const response = await this.httpClient.executeOperation(operation, args);
+      const durationMs = this.logDuration(name, startedAt);
+
+      const result = this.resultData(response);
+      // The outputSchema describes the response body, so a result unwrapped or wrapped along the way,
+      // e.g. with extracted headers or a content range, is not returned as structured content
+      const structured =
+        this.config.outputSchemas &&
+        result === response.data &&
+        this.getTool(name)?.outputSchema &&
+        typeof result === "object" &&
+        result !== null &&
+        !Array.isArray(result);
+
+      // Convert response to MCP format
+      return {
+        content: [this.resultContent(name, result, response.headers)],
+        ...(structured ? { structuredContent: result } : {}),
+        ...(this.config.includeResponseMetadata ? { _meta: { duration_ms: durationMs } } : {}),
+      };
+    } catch (error) {
+      // Request errors carry the request config with its credentials, so only the message is logged
+      console.error("Error in tool call:", error instanceof Error ? error.message : error);
+      const durationMs = this.logDuration(name, startedAt);
+      if (error instanceof HttpClientError) {
+        console.error(`HttpClientError encountered (${error.status}), returning structured error`);
+        const data = error.data?.response?.data ?? error.data ?? {};
+        if (this.config.returnErrorsAsResults) {
+          return {
+            content: [{ type: "text", text: this.serializeResult({ status: error.status, body: data }) }],
+            ...(this.config.includeResponseMetadata ? { _meta: { duration_ms: durationMs } } : {}),
+          };
+        }
         return {
           content: [
             {
-              type: "text", // currently this is the only type that seems to be used by mcp server
-              text: JSON.stringify(response.data), // TODO: pass through the http status code text?
+              type: "text",
+              text: this.serializeResult({
+                status: "error", // TODO: get this from http status code?
+                ...(typeof data === "object" ? data : { data: data }),
+              }),
             },
           ],
+          ...(this.config.includeResponseMetadata ? { _meta: { duration_ms: durationMs } } : {}),
         };
-      } catch (error) {
-        console.error("Error in tool call", error);
-        if (error instanceof HttpClientError) {
-          console.error("HttpClientError encountered, returning structured error", error);
-          const data = error.data?.response?.data ?? error.data ?? {};
-          return {
-            content: [
-              {
-                type: "text",
-                text: JSON.stringify({
-                  status: "error", // TODO: get this from http status code?
-                  ...(typeof data === "object" ? data : { data: data }),
-                }),
-              },
-            ],
-          };
-        }
-        throw error;
       }
+      throw error;
+    }
+  }
+
+  /**
+   * Call a tool by name and return the response data as is, for embedding the proxy in code that
+   * doesn't speak MCP. Unlike callTool, a failed request is thrown as an HttpClientError.
+   */
+  async invoke(name: string, params?: Record<string, unknown>): Promise<unknown> {
+    const operation = this.findOperation(name);
+    if (!operation) {
+      throw new Error(`Method ${name} not found`);
+    }
+    const startedAt = performance.now();
+    try {
+      return this.resultData(await this.httpClient.executeOperation(operation, this.withArgDefaults(name, params)));
+    } finally {
+      this.logDuration(name, startedAt);
+    }
+  }
+
+  // Configured defaults fill in the arguments a call omits, and never replace ones it passes
+  private withArgDefaults(name: string, params?: Record<string, unknown>): Record<string, unknown> | undefined {
+    const defaults = this.config.argDefaults?.[name];
+    if (!defaults) {
+      return params;
+    }
+    const merged: Record<string, unknown> = { ...params };
+    for (const [arg, value] of Object.entries(defaults)) {
+      if (merged[arg] === undefined) {
+        merged[arg] = value;
+      }
+    }
+    return merged;
+  }
+
+  // Partial content from a _range request carries which part of the resource it is, and configured
+  // response headers are passed along with the body when the response has any of them
+  private resultData(response: HttpClientResponse): unknown {
+    const data = this.unwrapResponse(response.data);
+    const result = response.status === 206 ? { content_range: response.headers.get("content-range"), data } : data;
+    const headers = this.extractHeaders(response.headers);
+    return headers ? { _headers: headers, body: result } : result;
+  }
+
+  private extractHeaders(headers: Headers): Record<string, string> | undefined {
+    const extracted: Record<string, string> = {};
+    for (const name of this.config.extractResponseHeaders ?? []) {
+      const value = headers?.get(name);
+      if (value !== null && value !== undefined) {
+        extracted[name] = value;
+      }
+    }
+    return Object.keys(extracted).length > 0 ? extracted : undefined;
+  }
+
+  // An envelope such as {"data": {...}} is replaced by its content when its key is configured for unwrapping
+  private unwrapResponse(data: unknown): unknown {
+    const key = this.config.unwrapResponseKey;
+    if (!key || typeof data !== "object" || data === null || Array.isArray(data)) {
+      return data;
+    }
+    const keys = Object.keys(data);
+    return keys.length === 1 && keys[0] === key ? (data as Record<string, unknown>)[key] : data;
+  }
+
+  /**
+   * Call a tool by the operationId it was generated from, with the same result as calling it by name
+   */
+  async callByOperationId(operationId: string, params?: Record<string, unknown>): Promise<CallToolResult> {
+    const name = this.operationIdIndex.get(operationId);
+    if (!name) {
+      throw new Error(`Operation ${operationId} not found`);
+    }
+    return this.callTool(name, params);
+  }
+
+  /**
+   * Reject a request whose payload exceeds the configured maximum size before doing any work for it
+   */
+  private checkRequestSize(params: unknown) {
+    const maxBytes = this.config.maxRequestBytes;
+    if (maxBytes === undefined) {
+      return;
+    }
+    const size = Buffer.byteLength(JSON.stringify(params) ?? "");
+    if (size > maxBytes) {
+      throw new McpError(ErrorCode.InvalidRequest, `Request payload of ${size} bytes exceeds the limit of ${maxBytes}`);
+    }
+  }
+
+  // String arguments are checked against the pattern their schema declares before any request is sent
+  private checkPatterns(name: string, args?: Record<string, unknown>) {
+    const properties = this.getToolSchema(name)?.properties as Record<string, IJsonSchema> | undefined;
+    for (const [arg, value] of Object.entries(args ?? {})) {
+      const pattern = properties?.[arg]?.pattern;
+      if (typeof value !== "string" || !pattern) {
+        continue;
+      }
+      const regex = this.compilePattern(pattern);
+      if (regex && !regex.test(value)) {
+        throw new McpError(
+          ErrorCode.InvalidParams,
+          `Argument ${arg} of ${name} is ${JSON.stringify(value)}, which does not match the pattern ${pattern}`,
+        );
+      }
+    }
+  }
+
+  // Compiled once per pattern. Patterns JavaScript can't compile are not checked
+  private compilePattern(pattern: string): RegExp | null {
+    if (!this.patterns.has(pattern)) {
+      try {
+        this.patterns.set(pattern, new RegExp(pattern, "u"));
+      } catch {
+        console.warn(`Ignoring pattern ${pattern}, which is not a valid regular expression`);
+        this.patterns.set(pattern, null);
+      }
+    }
+    return this.patterns.get(pattern)!;
+  }
+
+  // Calls beyond requestQueueDepth are turned away rather than piling up on the API
+  private acquireCallSlot() {
+    const depth = this.config.requestQueueDepth;
+    if (depth !== undefined && this.inFlightCalls >= depth) {
+      throw new McpError(SERVER_BUSY, `Server is busy with ${this.inFlightCalls} tool call(s), retry later`);
+    }
+    this.inFlightCalls++;
+  }
+
+  private logDuration(toolName: string, startedAt: number): number {
+    const durationMs = Math.round(performance.now() - startedAt);
+    if (isLevelEnabled("info")) {
+      console.error(`Tool ${toolName} finished in ${durationMs}ms`);
+    }
+    return durationMs;
+  }
+
+  /**
+   * A body the API sent as something other than JSON, e.g. markdown, is returned as it is with its
+   * media type as a hint for the client: as text for text/* types, as an embedded resource otherwise.
+   * Binary bodies, which the client keeps as bytes, are returned base64 encoded as an image or a blob
+   */
+  private resultContent(name: string, result: unknown, headers?: Headers): CallToolResult["content"][number] {
+    const mimeType = headers?.get("content-type")?.split(";")[0].trim().toLowerCase();
+    const uri = `anytype-mcp://tools/${name}/result`;
+    if (Buffer.isBuffer(result)) {
+      const data = result.toString("base64");
+      if (mimeType?.startsWith("image/")) {
+        return { type: "image", data, mimeType };
+      }
+      return { type: "resource", resource: { uri, mimeType: mimeType ?? "application/octet-stream", blob: data } };
+    }
+    if (!headers || !mimeType || typeof result !== "string" || /[/+]json$/.test(mimeType)) {
+      return { type: "text", text: this.serializeResult(result) };
+    }
+    const text = this.truncateResult(result);
+    if (this.getContentType(headers) === "text") {
+      return { type: "text", text, mimeType };
+    }
+    return { type: "resource", resource: { uri, mimeType, text } };
+  }
+
+  /**
+   * Serialize a tool result, compact, indented or as NDJSON, truncating it when it exceeds the configured maximum size
+   */
+  private serializeResult(data: unknown): string {
+    return this.truncateResult(
+      this.config.resultFormat === "ndjson" && Array.isArray(data)
+        ? data.map((item) => JSON.stringify(item)).join("\n")
+        : JSON.stringify(data, null, this.config.prettyResults ? 2 : undefined),
+    );
+  }
+
+  private truncateResult(text: string): string {
+    const maxChars = this.config.maxResultChars;
+    if (maxChars === undefined || text === undefined || text.length <= maxChars) {
+      return text;
+    }
+    console.error(`Tool result of ${text.length} chars exceeds the limit of ${maxChars}, truncating it`);
+    return `${text.slice(0, maxChars)}...[truncated ${text.length - maxChars} chars]`;
+  }
+
+  // What the server info tool returns, answered from the spec without calling the API
+  private describeApi() {
+    const toolCount = this.listTools().filter((tool) => tool.name !== SERVER_INFO_TOOL).length;
+    return { info: this.specInfo, toolCount, ...(this.specSource ? { specSource: this.specSource } : {}) };
+  }
+
+  /**
+   * List the converted tools as they are exposed to MCP clients
+   */
+  listTools(): Tool[] {
+    return [...this.listedTools];
+  }
+
+  private buildToolList(): Tool[] {
+    const tools: Tool[] = [];
+
+    // Add methods as separate tools to match the MCP format
+    Object.entries(this.tools).forEach(([toolName, def]) => {
+      def.methods.forEach((method) => {
+        const toolNameWithMethod = `${toolName}-${method.name}`;
+        const truncatedToolName = this.truncateToolName(toolNameWithMethod);
+        const outputSchema = this.config.outputSchemas ? this.listedOutputSchema(method.outputSchema) : undefined;
+        tools.push({
+          name: truncatedToolName,
+          description: method.description,
+          inputSchema: method.inputSchema as Tool["inputSchema"],
+          ...(outputSchema ? { outputSchema } : {}),
+        });
+      });
     });
+
+    if (this.config.exposeServerInfoTool) {
+      tools.push({
+        name: SERVER_INFO_TOOL,
+        description: "Get the title, version and description of the API this server exposes, and its number of tools",
+        inputSchema: { type: "object", properties: {} },
+      });
+    }
+
+    return tools;
   }
 
-  private findOperation(operationId: string): (OpenAPIV3.OperationObject & { method: string; path: string }) | null {
+  // MCP only takes object output schemas, and the converter leaves an empty $defs behind
+  private listedOutputSchema(outputSchema?: IJsonSchema): IJsonSchema | undefined {
+    if (outputSchema?.type !== "object") {
+      return undefined;
+    }
+    const { $defs, ...schema } = outputSchema;
+    return $defs && Object.keys($defs).length > 0 ? outputSchema : schema;
+  }
+
+  /**
+   * List the tools backed by an operation with the given HTTP method, matched case-insensitively
+   */
+  toolsByMethod(method: string): Tool[] {
+    const wanted = method.toLowerCase();
+    return this.listTools().filter((tool) => this.findOperation(tool.name)?.method.toLowerCase() === wanted);
+  }
+
+  /**
+   * Look up a tool by the name it is listed under
+   */
+  getTool(name: string): Tool | undefined {
+    return this.toolsByName.get(name);
+  }
+
+  /**
+   * Get the converted MCP input schema of a tool
+   */
+  getToolSchema(name: string): Tool["inputSchema"] | undefined {
+    return this.getTool(name)?.inputSchema;
+  }
+
+  /**
+   * Machine-readable summary of the server and the tools it exposes
+   */
+  getServerInfo(): ServerInfo {
+    const toolNames = this.listTools().map((tool) => tool.name);
+    return {
+      name: this.name,
+      version: SERVER_VERSION,
+      mode: "stdio",
+      toolCount: toolNames.length,
+      toolNames,
+      skippedOperations: this.skippedOperations,
+      ...(this.specSource ? { specSource: this.specSource } : {}),
+    };
+  }
+
+  /**
+   * Find the OpenAPI operation behind a tool name
+   */
+  findOperation(operationId: string): (OpenAPIV3.OperationObject & { method: string; path: string }) | null {
     return this.openApiLookup[operationId] ?? null;
   }
 
+  /**
+   * Map a tool name, as exposed to MCP clients, back to the operationId it was generated from
+   */
+  resolveOperationId(toolName: string): string | undefined {
+    for (const [apiName, def] of Object.entries(this.tools)) {
+      const method = def.methods.find((candidate) => {
+        const name = `${apiName}-${candidate.name}`;
+        return name === toolName || this.truncateToolName(name) === toolName;
+      });
+      if (method) {
+        return method.operationId;
+      }
+    }
+    return undefined;
+  }
+
   private parseHeadersFromEnv(): Record<string, string> {
     const headersJson = process.env.OPENAPI_MCP_HEADERS;
     if (!headersJson) {
@@ -163,6 +687,20 @@ export class MCPProxy {
     return name.slice(0, 64);
   }
 
+  /**
+   * Drop cached responses without restarting the server
+   */
+  clearCache() {
+    this.httpClient.clearCache();
+  }
+
+  /**
+   * Prime the connection to the API so the first tool call doesn't pay for connection setup
+   */
+  async warmup() {
+    await this.httpClient.warmup();
+  }
+
   async connect(transport: Transport) {
     // The SDK will handle stdio communication
     await this.server.connect(transport);
@@ -1,7 +1,7 @@
 import { JSONSchema7 as IJsonSchema } from "json-schema";
 import { OpenAPIV3 } from "openapi-types";
 import { describe, expect, it } from "vitest";
-import { OpenAPIToMCPConverter } from "../parser";
+import { NameCase, OpenAPIToMCPConverter } from "../parser";
 
 interface ToolMethod {
   name: string;
@@ -848,6 +848,438 @@ describe("OpenAPIToMCPConverter", () => {
       description: "A schema description",
     });
   });
+
+  it("reports operations that fail to convert while converting the rest", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/pets": {
+          get: {
+            operationId: "listPets",
+            responses: { "200": { description: "OK" } },
+          },
+          post: {
+            operationId: "createPet",
+            parameters: [null as unknown as OpenAPIV3.ParameterObject],
+            responses: { "201": { description: "Created" } },
+          },
+        },
+        "/owners": {
+          get: {
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    const converter = new OpenAPIToMCPConverter(spec);
+    const { tools, skipped } = converter.convertToMCPTools();
+
+    expect(tools.API.methods.map((m) => m.name)).toEqual(["listPets"]);
+    expect(skipped).toEqual([
+      { method: "post", path: "/pets", operationId: "createPet", reason: expect.any(String) },
+      { method: "get", path: "/owners", reason: "missing operationId" },
+    ]);
+  });
+
+  it("collects a schema referenced twice under $defs when useDefs is enabled", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/orders": {
+          post: {
+            operationId: "createOrder",
+            requestBody: {
+              content: {
+                "application/json": {
+                  schema: {
+                    type: "object",
+                    properties: {
+                      billing: { $ref: "#/components/schemas/Address" },
+                      shipping: { $ref: "#/components/schemas/Address" },
+                    },
+                  },
+                },
+              },
+            },
+            responses: { "201": { description: "Created" } },
+          },
+        },
+      },
+      components: {
+        schemas: {
+          Address: {
+            type: "object",
+            properties: { street: { type: "string" }, city: { type: "string" } },
+          },
+        },
+      },
+    };
+
+    const withDefs = new OpenAPIToMCPConverter(spec, { useDefs: true }).convertToMCPTools();
+    const inputSchema = withDefs.tools.API.methods[0].inputSchema;
+
+    expect(inputSchema.properties?.billing).toEqual({ $ref: "#/$defs/Address" });
+    expect(inputSchema.properties?.shipping).toEqual({ $ref: "#/$defs/Address" });
+    expect(Object.keys(inputSchema.$defs ?? {})).toEqual(["Address"]);
+    expect(inputSchema.$defs?.Address).toEqual({
+      type: "object",
+      properties: { street: { type: "string" }, city: { type: "string" } },
+      additionalProperties: true,
+    });
+
+    const inlined = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    expect(inlined.tools.API.methods[0].inputSchema.properties?.billing).toEqual(inputSchema.$defs?.Address);
+    expect(inlined.tools.API.methods[0].inputSchema.$defs).toEqual({});
+  });
+
+  it("merges path-level parameters into each operation, letting the operation override them", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/objects/{id}": {
+          parameters: [{ name: "id", in: "path", required: true, schema: { type: "string" } }],
+          get: {
+            operationId: "getObject",
+            responses: { "200": { description: "OK" } },
+          },
+          patch: {
+            operationId: "updateObject",
+            parameters: [
+              {
+                name: "id",
+                in: "path",
+                required: true,
+                description: "The object to update",
+                schema: { type: "string" },
+              },
+            ],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    const { tools, openApiLookup } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    const [getObject, updateObject] = tools.API.methods;
+
+    expect(getObject.inputSchema.properties?.id).toEqual({ type: "string" });
+    expect(getObject.inputSchema.required).toEqual(["id"]);
+    expect(updateObject.inputSchema.properties?.id).toEqual({ type: "string", description: "The object to update" });
+    expect(updateObject.inputSchema.required).toEqual(["id"]);
+    expect(openApiLookup["API-getObject"].parameters).toHaveLength(1);
+    expect(openApiLookup["API-updateObject"].parameters).toHaveLength(1);
+  });
+
+  it("preserves integer and number enums in converted parameters", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/exports": {
+          get: {
+            operationId: "listExports",
+            parameters: [
+              { name: "priority", in: "query", schema: { type: "integer", enum: [1, 2, 3] } },
+              { name: "ratio", in: "query", schema: { type: "number", enum: [0.5, 1.5] } },
+            ],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    const { tools } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    const properties = tools.API.methods[0].inputSchema.properties;
+
+    expect(properties?.priority).toEqual({ type: "integer", enum: [1, 2, 3] });
+    expect(properties?.ratio).toEqual({ type: "number", enum: [0.5, 1.5] });
+  });
+
+  it("emits const values of fixed-value parameters", () => {
+    const spec = {
+      openapi: "3.1.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/spaces": {
+          get: {
+            operationId: "listSpaces",
+            parameters: [{ name: "version", in: "query", schema: { type: "string", const: "v1" } }],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    } as OpenAPIV3.Document;
+
+    const { tools } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+
+    expect(tools.API.methods[0].inputSchema.properties?.version).toEqual({ type: "string", const: "v1" });
+  });
+
+  it("normalizes path keys without a leading slash", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      servers: [{ url: "http://localhost:31009/v1" }],
+      paths: {
+        "spaces/{id}": {
+          get: {
+            operationId: "getSpace",
+            parameters: [{ name: "id", in: "path", required: true, schema: { type: "string" } }],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    const { openApiLookup } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    const { path } = openApiLookup["API-getSpace"];
+
+    expect(path).toBe("/spaces/{id}");
+    expect(`${spec.servers![0].url}${path}`).toBe("http://localhost:31009/v1/spaces/{id}");
+  });
+
+  it("merges allOf object subschemas into one object when mergeAllOf is enabled", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {},
+      components: {
+        schemas: {
+          Named: {
+            type: "object",
+            properties: { name: { type: "string" } },
+            required: ["name"],
+          },
+        },
+      },
+    };
+    const schema: OpenAPIV3.SchemaObject = {
+      allOf: [
+        { $ref: "#/components/schemas/Named" },
+        { type: "object", properties: { icon: { type: "string" }, layout: { type: "string" } }, required: ["layout"] },
+      ],
+    };
+
+    const merged = new OpenAPIToMCPConverter(spec, { mergeAllOf: true }).convertOpenApiSchemaToJsonSchema(
+      schema,
+      new Set(),
+    );
+
+    expect(merged).toEqual({
+      type: "object",
+      properties: { name: { type: "string" }, icon: { type: "string" }, layout: { type: "string" } },
+      required: ["name", "layout"],
+      additionalProperties: true,
+    });
+    expect(new OpenAPIToMCPConverter(spec).convertOpenApiSchemaToJsonSchema(schema, new Set()).allOf).toHaveLength(2);
+  });
+
+  it("stops at a circular reference with a placeholder", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {},
+      components: {
+        schemas: {
+          Node: {
+            type: "object",
+            properties: {
+              name: { type: "string" },
+              parent: { $ref: "#/components/schemas/Node" },
+            },
+          },
+        },
+      },
+    };
+
+    const converted = new OpenAPIToMCPConverter(spec).convertOpenApiSchemaToJsonSchema(
+      { $ref: "#/components/schemas/Node" },
+      new Set(),
+    );
+
+    expect(converted.properties?.parent).toEqual({ type: "object", description: "circular reference to Node" });
+    expect(converted.properties?.name).toEqual({ type: "string" });
+  });
+
+  it("takes binary request bodies as a base64-encoded body argument", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/files": {
+          post: {
+            operationId: "uploadRaw",
+            requestBody: {
+              content: { "application/octet-stream": { schema: { type: "string", format: "binary" } } },
+            },
+            responses: { "201": { description: "Created" } },
+          },
+        },
+      },
+    };
+
+    const { tools } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+
+    expect(tools.API.methods[0].inputSchema.properties?.body).toEqual({
+      type: "string",
+      contentEncoding: "base64",
+      description: "base64-encoded application/octet-stream content",
+    });
+    expect(tools.API.methods[0].inputSchema.required).toEqual(["body"]);
+  });
+
+  it("applies the configured casing to tool names", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/users/{id}": { get: { operationId: "getUserByID", responses: { "200": { description: "OK" } } } },
+        "/users": { get: { operationId: "list_all-users", responses: { "200": { description: "OK" } } } },
+      },
+    };
+    const names = (nameCase?: NameCase) =>
+      new OpenAPIToMCPConverter(spec, { nameCase }).convertToMCPTools().tools.API.methods.map((m) => m.name);
+
+    expect(names()).toEqual(["getUserByID", "list-all-users"]);
+    expect(names("snake")).toEqual(["get_user_by_id", "list_all_users"]);
+    expect(names("camel")).toEqual(["getUserById", "listAllUsers"]);
+  });
+
+  it("keeps additionalProperties of a flattened body so extra keys are allowed", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/objects": {
+          post: {
+            operationId: "createObject",
+            requestBody: {
+              content: {
+                "application/json": {
+                  schema: {
+                    type: "object",
+                    required: ["name"],
+                    properties: { name: { type: "string" } },
+                    additionalProperties: true,
+                  },
+                },
+              },
+            },
+            responses: { "201": { description: "Created" } },
+          },
+        },
+        "/tags": {
+          post: {
+            operationId: "createTag",
+            requestBody: {
+              content: {
+                "application/json": {
+                  schema: { type: "object", properties: { name: { type: "string" } } },
+                },
+              },
+            },
+            responses: { "201": { description: "Created" } },
+          },
+        },
+      },
+    };
+
+    const { tools } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    const [createObject, createTag] = tools.API.methods;
+
+    expect(createObject.inputSchema.additionalProperties).toBe(true);
+    expect(createObject.inputSchema.required).toEqual(["name"]);
+    expect(createTag.inputSchema).not.toHaveProperty("additionalProperties");
+  });
+
+  it("represents the required fields of a referenced request body and its nested objects", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/tasks": {
+          post: {
+            operationId: "createTask",
+            requestBody: {
+              content: { "application/json": { schema: { $ref: "#/components/schemas/Task" } } },
+            },
+            responses: { "201": { description: "Created" } },
+          },
+        },
+      },
+      components: {
+        schemas: {
+          Task: {
+            type: "object",
+            required: ["title", "details"],
+            properties: {
+              title: { type: "string" },
+              details: { $ref: "#/components/schemas/Details" },
+            },
+          },
+          Details: {
+            type: "object",
+            required: ["due"],
+            properties: { due: { type: "string", format: "date" }, priority: { type: "integer" } },
+          },
+        },
+      },
+    };
+
+    const { tools } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+    const inputSchema = tools.API.methods[0].inputSchema;
+
+    expect(inputSchema.required).toEqual(["title", "details"]);
+    expect(inputSchema.properties?.details).toEqual({
+      type: "object",
+      required: ["due"],
+      properties: { due: { type: "string", format: "date" }, priority: { type: "integer" } },
+      additionalProperties: true,
+    });
+  });
+
+  it("skips delete operations, also those that declare a request body", () => {
+    const spec: OpenAPIV3.Document = {
+      openapi: "3.0.0",
+      info: { title: "Test API", version: "1.0.0" },
+      paths: {
+        "/spaces/{spaceId}/objects": {
+          delete: {
+            operationId: "deleteObjects",
+            parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+            requestBody: {
+              content: {
+                "application/json": {
+                  schema: {
+                    type: "object",
+                    required: ["object_ids"],
+                    properties: { object_ids: { type: "array", items: { type: "string" } } },
+                  },
+                },
+              },
+            },
+            responses: { "200": { description: "OK" } },
+          },
+        },
+        "/spaces/{spaceId}": {
+          delete: {
+            operationId: "deleteSpace",
+            parameters: [{ name: "spaceId", in: "path", required: true, schema: { type: "string" } }],
+            responses: { "200": { description: "OK" } },
+          },
+        },
+      },
+    };
+
+    const { tools, openApiLookup } = new OpenAPIToMCPConverter(spec).convertToMCPTools();
+
+    expect(tools.API.methods).toEqual([]);
+    expect(openApiLookup).toEqual({});
+  });
 });
 
 // Additional complex test scenarios as a table test
@@ -0,0 +1,73 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { OpenAPIV3 } from "openapi-types";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { OpenAPIToMCPConverter } from "../parser";
+import { convertWithCache } from "../tool-cache";
+
+const packageJson = vi.hoisted(() => ({ version: "1.0.0" }));
+vi.mock("../../../package.json", () => ({ default: packageJson }));
+
+function specWith(operationId: string): OpenAPIV3.Document {
+  return {
+    openapi: "3.0.0",
+    info: { title: "Test API", version: "1.0.0" },
+    paths: {
+      "/spaces": {
+        get: { operationId, responses: { "200": { description: "OK" } } },
+      },
+    },
+  };
+}
+
+describe("convertWithCache", () => {
+  let dir: string;
+  let cachePath: string;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), "anytype-mcp-tool-cache-"));
+    cachePath = path.join(dir, "tools.json");
+  });
+
+  afterEach(() => {
+    packageJson.version = "1.0.0";
+    fs.rmSync(dir, { recursive: true });
+    vi.restoreAllMocks();
+  });
+
+  it("reuses the cached tools while the spec is unchanged", () => {
+    const convertSpy = vi.spyOn(OpenAPIToMCPConverter.prototype, "convertToMCPTools");
+
+    const first = convertWithCache(specWith("listSpaces"), {}, cachePath);
+    const second = convertWithCache(specWith("listSpaces"), {}, cachePath);
+
+    expect(convertSpy).toHaveBeenCalledTimes(1);
+    expect(second).toEqual(first);
+    expect(second.tools.API.methods.map((method) => method.name)).toEqual(["listSpaces"]);
+  });
+
+  it("converts the spec again once it changes", () => {
+    const convertSpy = vi.spyOn(OpenAPIToMCPConverter.prototype, "convertToMCPTools");
+
+    convertWithCache(specWith("listSpaces"), {}, cachePath);
+    const result = convertWithCache(specWith("getSpaces"), {}, cachePath);
+
+    expect(convertSpy).toHaveBeenCalledTimes(2);
+    expect(result.tools.API.methods.map((method) => method.name)).toEqual(["getSpaces"]);
+    expect(Object.keys(convertWithCache(specWith("getSpaces"), {}, cachePath).openApiLookup)).toEqual([
+      "API-getSpaces",
+    ]);
+    expect(convertSpy).toHaveBeenCalledTimes(2);
+  });
+
+  it("converts the spec again after an upgrade", () => {
+    const convertSpy = vi.spyOn(OpenAPIToMCPConverter.prototype, "convertToMCPTools");
+
+    convertWithCache(specWith("listSpaces"), {}, cachePath);
+    packageJson.version = "1.1.0";
+    convertWithCache(specWith("listSpaces"), {}, cachePath);
+
+    expect(convertSpy).toHaveBeenCalledTimes(2);
+  });
+});
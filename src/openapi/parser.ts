@@ -5,11 +5,32 @@ import type { OpenAPIV3, OpenAPIV3_1 } from "openapi-types";
 
 type NewToolMethod = {
   name: string;
+  // The original operationId, kept as is while name gets sanitized for MCP
+  operationId: string;
   description: string;
   inputSchema: IJsonSchema & { type: "object" };
   outputSchema?: IJsonSchema;
 };
 
+export type SkippedOperation = {
+  method: string;
+  path: string;
+  operationId?: string;
+  reason: string;
+};
+
+export const NAME_CASES = ["preserve", "snake", "camel"] as const;
+export type NameCase = (typeof NAME_CASES)[number];
+
+export type ConverterOptions = {
+  // Collect component schemas referenced from tool input schemas under $defs instead of inlining them
+  useDefs?: boolean;
+  // Merge allOf object subschemas into a single object schema, for clients that don't combine them
+  mergeAllOf?: boolean;
+  // Casing of tool names. With preserve, the default, operationIds are kept apart from _ becoming -
+  nameCase?: NameCase;
+};
+
 type FunctionParameters = {
   type: "object";
   properties?: Record<string, unknown>;
@@ -17,11 +38,57 @@ type FunctionParameters = {
   [key: string]: unknown;
 };
 
+/**
+ * Combine object schemas into one with the union of their properties and required fields.
+ * Returns null when any of them is not a plain object schema, e.g. an unresolved $ref.
+ */
+function mergeObjectSchemas(schemas: IJsonSchema[]): IJsonSchema | null {
+  if (schemas.length === 0 || !schemas.every((s) => !s.$ref && (s.type === "object" || s.properties))) {
+    return null;
+  }
+  return {
+    type: "object",
+    description: schemas.find((s) => s.description)?.description,
+    properties: Object.assign({}, ...schemas.map((s) => s.properties ?? {})),
+    required: [...new Set(schemas.flatMap((s) => s.required ?? []))],
+    // Extra properties are only rejected when every part rejects them
+    additionalProperties: schemas.every((s) => s.additionalProperties === false) ? false : true,
+  };
+}
+
+/**
+ * Ensure a path starts with exactly one slash
+ */
+function normalizePath(path: string): string {
+  return `/${path.replace(/^\/+/, "")}`;
+}
+
+// Split an identifier in any casing, e.g. getUserByID or get-user_by-id, into its words
+function identifierWords(name: string): string[] {
+  return name
+    .replace(/([a-z0-9])([A-Z])/g, "$1 $2")
+    .replace(/([A-Z]+)([A-Z][a-z])/g, "$1 $2")
+    .split(/[^A-Za-z0-9]+/)
+    .filter(Boolean)
+    .map((word) => word.toLowerCase());
+}
+
+// The content type of a request body declared as a binary string, sent as raw bytes by the client
+function binaryContentType(body: OpenAPIV3.RequestBodyObject): string | undefined {
+  return Object.entries(body.content).find(([, media]) => {
+    const schema = media.schema as OpenAPIV3.SchemaObject | undefined;
+    return schema?.type === "string" && schema.format === "binary";
+  })?.[0];
+}
+
 export class OpenAPIToMCPConverter {
   private schemaCache: Record<string, IJsonSchema> = {};
   private nameCounter: number = 0;
 
-  constructor(private openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document) {}
+  constructor(
+    private openApiSpec: OpenAPIV3.Document | OpenAPIV3_1.Document,
+    private options: ConverterOptions = {},
+  ) {}
 
   /**
    * Resolve a $ref reference to its schema in the openApiSpec.
@@ -47,7 +114,7 @@ export class OpenAPIToMCPConverter {
 
   /**
    * Convert an OpenAPI schema (or reference) into a JSON Schema object.
-   * Uses caching and handles cycles by returning $ref nodes.
+   * Uses caching and handles cycles by returning a placeholder object schema.
    */
   convertOpenApiSchemaToJsonSchema(
     schema: OpenAPIV3.SchemaObject | OpenAPIV3.ReferenceObject,
@@ -77,9 +144,15 @@ export class OpenAPIToMCPConverter {
         return this.schemaCache[ref];
       }
 
+      // resolvedRefs holds the references being expanded above this one, so meeting one again is a cycle
+      if (resolvedRefs.has(ref)) {
+        const name = ref.split("/").pop();
+        console.warn(`Not expanding circular reference to ${name}`);
+        return { type: "object", description: `circular reference to ${name}` };
+      }
+
       const resolved = this.internalResolveRef(ref, resolvedRefs);
       if (!resolved) {
-        // TODO: need extensive tests for this and we definitely need to handle the case of self references
         console.error(`Failed to resolve ref ${ref}`);
         return {
           $ref: ref.replace(/^#\/components\/schemas\//, "#/$defs/"),
@@ -87,6 +160,7 @@ export class OpenAPIToMCPConverter {
         };
       } else {
         const converted = this.convertOpenApiSchemaToJsonSchema(resolved, resolvedRefs, resolveRefs);
+        resolvedRefs.delete(ref);
         this.schemaCache[ref] = converted;
 
         return converted;
@@ -118,10 +192,19 @@ export class OpenAPIToMCPConverter {
       result.enum = schema.enum;
     }
 
+    if (schema.pattern) {
+      result.pattern = schema.pattern;
+    }
+
     if (schema.default !== undefined) {
       result.default = schema.default;
     }
 
+    // const is an OpenAPI 3.1 keyword, missing from the 3.0 types
+    if ("const" in schema && schema.const !== undefined) {
+      result.const = schema.const as IJsonSchema["const"];
+    }
+
     // Handle object properties
     if (schema.type === "object") {
       result.type = "object";
@@ -302,7 +385,20 @@ export class OpenAPIToMCPConverter {
       result.anyOf = schema.anyOf.map((s) => this.convertOpenApiSchemaToJsonSchema(s, resolvedRefs, resolveRefs));
     }
     if (schema.allOf) {
-      result.allOf = schema.allOf.map((s) => this.convertOpenApiSchemaToJsonSchema(s, resolvedRefs, resolveRefs));
+      const subschemas = schema.allOf.map((s) => this.convertOpenApiSchemaToJsonSchema(s, resolvedRefs, resolveRefs));
+      const merged = this.options.mergeAllOf ? mergeObjectSchemas(subschemas) : null;
+      if (merged) {
+        result.type = "object";
+        result.description ??= merged.description;
+        result.properties = { ...result.properties, ...merged.properties };
+        const required = [...new Set([...(result.required ?? []), ...(merged.required ?? [])])];
+        if (required.length > 0) {
+          result.required = required;
+        }
+        result.additionalProperties = merged.additionalProperties;
+      } else {
+        result.allOf = subschemas;
+      }
     }
 
     return result;
@@ -312,6 +408,7 @@ export class OpenAPIToMCPConverter {
     tools: Record<string, { methods: NewToolMethod[] }>;
     openApiLookup: Record<string, OpenAPIV3.OperationObject & { method: string; path: string }>;
     zip: Record<string, { openApi: OpenAPIV3.OperationObject & { method: string; path: string }; mcp: NewToolMethod }>;
+    skipped: SkippedOperation[];
   } {
     const apiName = "API";
 
@@ -323,8 +420,11 @@ export class OpenAPIToMCPConverter {
       string,
       { openApi: OpenAPIV3.OperationObject & { method: string; path: string }; mcp: NewToolMethod }
     > = {};
-    for (const [path, pathItem] of Object.entries(this.openApiSpec.paths || {})) {
+    const skipped: SkippedOperation[] = [];
+    for (const [rawPath, pathItem] of Object.entries(this.openApiSpec.paths || {})) {
       if (!pathItem) continue;
+      // Hand-written specs sometimes omit the leading slash, which would glue the path onto the base URL
+      const path = normalizePath(rawPath);
 
       for (const [method, operation] of Object.entries(pathItem)) {
         // skip "Auth" operations and delete operations, as they shouldn't be called by mcp client
@@ -335,19 +435,35 @@ export class OpenAPIToMCPConverter {
         )
           continue;
 
-        const mcpMethod = this.convertOperationToMCPMethod(operation, method, path);
-        if (mcpMethod) {
-          // convert name to kebab-case to conform mcp tool naming convention
-          const uniqueName = this.ensureUniqueName(mcpMethod.name).replaceAll("_", "-");
+        const merged = this.withPathParameters(pathItem, operation);
+        let mcpMethod: NewToolMethod | null;
+        try {
+          mcpMethod = this.convertOperationToMCPMethod(merged, method, path);
+        } catch (error) {
+          const reason = error instanceof Error ? error.message : String(error);
+          skipped.push({ method, path, operationId: operation.operationId, reason });
+          continue;
+        }
+        if (!mcpMethod) {
+          skipped.push({ method, path, reason: "missing operationId" });
+        } else {
+          const uniqueName = this.ensureUniqueName(this.toolName(mcpMethod.name));
           mcpMethod.name = uniqueName;
           tools[apiName]!.methods.push(mcpMethod);
-          openApiLookup[apiName + "-" + uniqueName] = { ...operation, method, path };
-          zip[apiName + "-" + uniqueName] = { openApi: { ...operation, method, path }, mcp: mcpMethod };
+          openApiLookup[apiName + "-" + uniqueName] = { ...merged, method, path };
+          zip[apiName + "-" + uniqueName] = { openApi: { ...merged, method, path }, mcp: mcpMethod };
         }
       }
     }
 
-    return { tools, openApiLookup, zip };
+    if (skipped.length > 0) {
+      console.warn(
+        `Skipped ${skipped.length} operation(s) that could not be converted:\n` +
+          skipped.map((op) => `  ${op.method.toUpperCase()} ${op.path}: ${op.reason}`).join("\n"),
+      );
+    }
+
+    return { tools, openApiLookup, zip, skipped };
   }
 
   /**
@@ -368,7 +484,11 @@ export class OpenAPIToMCPConverter {
         )
           continue;
 
-        const parameters = this.convertOperationToJsonSchema(operation, method, path);
+        const parameters = this.convertOperationToJsonSchema(
+          this.withPathParameters(pathItem, operation),
+          method,
+          path,
+        );
         const tool: ChatCompletionTool = {
           type: "function",
           function: {
@@ -402,7 +522,11 @@ export class OpenAPIToMCPConverter {
         )
           continue;
 
-        const parameters = this.convertOperationToJsonSchema(operation, method, path);
+        const parameters = this.convertOperationToJsonSchema(
+          this.withPathParameters(pathItem, operation),
+          method,
+          path,
+        );
         const tool: Tool = {
           name: operation.operationId!,
           description: operation.summary || operation.description || "",
@@ -488,6 +612,29 @@ export class OpenAPIToMCPConverter {
     return ["get", "post", "put", "delete", "patch"].includes(method.toLowerCase());
   }
 
+  /**
+   * Merge parameters declared on the path item into the operation's own, which take precedence
+   * on a name and location clash. The operation is returned as is when the path declares none.
+   */
+  private withPathParameters(
+    pathItem: OpenAPIV3.PathItemObject | OpenAPIV3_1.PathItemObject,
+    operation: OpenAPIV3.OperationObject,
+  ): OpenAPIV3.OperationObject {
+    if (!pathItem.parameters?.length) {
+      return operation;
+    }
+    const key = (param: OpenAPIV3.ParameterObject | OpenAPIV3.ReferenceObject) => {
+      const resolved = this.resolveParameter(param);
+      return resolved ? `${resolved.in}:${resolved.name}` : undefined;
+    };
+    const operationParameters = operation.parameters ?? [];
+    const overridden = new Set(operationParameters.map(key));
+    const pathParameters = (pathItem.parameters as Array<OpenAPIV3.ParameterObject | OpenAPIV3.ReferenceObject>).filter(
+      (param) => !overridden.has(key(param)),
+    );
+    return { ...operation, parameters: [...pathParameters, ...operationParameters] };
+  }
+
   private isParameterObject(
     param: OpenAPIV3.ParameterObject | OpenAPIV3.ReferenceObject,
   ): param is OpenAPIV3.ParameterObject {
@@ -554,8 +701,10 @@ export class OpenAPIToMCPConverter {
 
     const methodName = operation.operationId;
 
+    // Only filled in with the useDefs option, otherwise component schemas are inlined to keep each tool self-contained
+    const defs: Record<string, IJsonSchema> = {};
     const inputSchema: IJsonSchema & { type: "object" } = {
-      $defs: {}, // Omit this.convertComponentsToJsonSchema() to reduce definition size
+      $defs: defs,
       type: "object",
       properties: {},
       required: [],
@@ -570,7 +719,7 @@ export class OpenAPIToMCPConverter {
           if (paramObj.name === "Anytype-Version") {
             continue;
           }
-          const schema = this.convertOpenApiSchemaToJsonSchema(paramObj.schema, new Set(), true);
+          const schema = this.convertInputSchema(paramObj.schema, defs);
           // Merge parameter-level description if available
           if (paramObj.description) {
             schema.description = paramObj.description;
@@ -591,11 +740,7 @@ export class OpenAPIToMCPConverter {
         // We convert the multipart/form-data schema to a JSON schema and we require
         // that the user passes in a string for each file that points to the local file
         if (bodyObj.content["multipart/form-data"]?.schema) {
-          const formSchema = this.convertOpenApiSchemaToJsonSchema(
-            bodyObj.content["multipart/form-data"].schema,
-            new Set(),
-            true,
-          );
+          const formSchema = this.convertInputSchema(bodyObj.content["multipart/form-data"].schema, defs);
           if (formSchema.type === "object" && formSchema.properties) {
             for (const [name, propSchema] of Object.entries(formSchema.properties)) {
               inputSchema.properties![name] = propSchema;
@@ -605,13 +750,19 @@ export class OpenAPIToMCPConverter {
             }
           }
         }
+        // Handle raw binary bodies, e.g. application/octet-stream, passed base64-encoded under "body"
+        else if (binaryContentType(bodyObj)) {
+          inputSchema.properties!["body"] = {
+            type: "string",
+            contentEncoding: "base64",
+            description: `base64-encoded ${binaryContentType(bodyObj)} content`,
+          };
+          inputSchema.required!.push("body");
+        }
         // Handle application/json
         else if (bodyObj.content["application/json"]?.schema) {
-          const bodySchema = this.convertOpenApiSchemaToJsonSchema(
-            bodyObj.content["application/json"].schema,
-            new Set(),
-            true,
-          );
+          const rawBodySchema = bodyObj.content["application/json"].schema;
+          const bodySchema = this.convertInputSchema(rawBodySchema, defs);
           // Merge body schema into the inputSchema's properties
           if (bodySchema.type === "object" && bodySchema.properties) {
             for (const [name, propSchema] of Object.entries(bodySchema.properties)) {
@@ -620,6 +771,12 @@ export class OpenAPIToMCPConverter {
             if (bodySchema.required) {
               inputSchema.required!.push(...bodySchema.required!);
             }
+            // A body explicitly open to extra keys, e.g. free-form metadata, keeps saying so once flattened
+            const declaredBody =
+              "$ref" in rawBodySchema ? this.internalResolveRef(rawBodySchema.$ref, new Set()) : rawBodySchema;
+            if (declaredBody?.additionalProperties) {
+              inputSchema.additionalProperties = bodySchema.additionalProperties;
+            }
           } else {
             // If the request body is not an object, just put it under "body"
             inputSchema.properties!["body"] = bodySchema;
@@ -657,6 +814,7 @@ export class OpenAPIToMCPConverter {
 
       return {
         name: methodName,
+        operationId: methodName,
         description,
         inputSchema,
         ...(outputSchema ? { outputSchema } : {}),
@@ -666,6 +824,7 @@ export class OpenAPIToMCPConverter {
       // Fallback to a basic object schema
       return {
         name: methodName,
+        operationId: methodName,
         description,
         inputSchema,
         ...(outputSchema ? { outputSchema } : {}),
@@ -673,6 +832,55 @@ export class OpenAPIToMCPConverter {
     }
   }
 
+  /**
+   * Convert a parameter or request body schema for a tool's input schema. With the useDefs option,
+   * nested component schemas are kept as local $refs and their definitions collected into defs.
+   */
+  private convertInputSchema(
+    schema: OpenAPIV3.SchemaObject | OpenAPIV3.ReferenceObject,
+    defs: Record<string, IJsonSchema>,
+  ): IJsonSchema {
+    if (!this.options.useDefs) {
+      return this.convertOpenApiSchemaToJsonSchema(schema, new Set(), true);
+    }
+    // The top-level schema is always resolved, its properties get merged into the input schema
+    const root = "$ref" in schema ? this.internalResolveRef(schema.$ref, new Set()) : schema;
+    if (!root) {
+      return this.convertOpenApiSchemaToJsonSchema(schema, new Set(), true);
+    }
+    const converted = this.convertOpenApiSchemaToJsonSchema(root, new Set(), false);
+    this.collectDefs(converted, defs);
+    return converted;
+  }
+
+  private collectDefs(node: unknown, defs: Record<string, IJsonSchema>) {
+    if (Array.isArray(node)) {
+      node.forEach((item) => this.collectDefs(item, defs));
+      return;
+    }
+    if (typeof node !== "object" || node === null) {
+      return;
+    }
+    for (const [key, value] of Object.entries(node)) {
+      if (key !== "$ref" || typeof value !== "string" || !value.startsWith("#/$defs/")) {
+        this.collectDefs(value, defs);
+        continue;
+      }
+      const name = value.slice("#/$defs/".length);
+      const component = this.openApiSpec.components?.schemas?.[name] as
+        | OpenAPIV3.SchemaObject
+        | OpenAPIV3.ReferenceObject
+        | undefined;
+      if (name in defs || !component) {
+        continue;
+      }
+      // Register the name before converting, so self-referencing schemas don't recurse forever
+      defs[name] = {};
+      defs[name] = this.convertOpenApiSchemaToJsonSchema(component, new Set(), false);
+      this.collectDefs(defs[name], defs);
+    }
+  }
+
   private extractResponseType(responses: OpenAPIV3.ResponsesObject | undefined): IJsonSchema | null {
     // Look for a success response
     const successResponse = responses?.["200"] || responses?.["201"] || responses?.["202"] || responses?.["204"];
@@ -706,6 +914,20 @@ export class OpenAPIToMCPConverter {
     return { type: "string", description: responseObj.description || "" };
   }
 
+  private toolName(operationId: string): string {
+    switch (this.options.nameCase) {
+      case "snake":
+        return identifierWords(operationId).join("_");
+      case "camel":
+        return identifierWords(operationId)
+          .map((word, i) => (i === 0 ? word : word[0].toUpperCase() + word.slice(1)))
+          .join("");
+      default:
+        // convert name to kebab-case to conform mcp tool naming convention
+        return operationId.replaceAll("_", "-");
+    }
+  }
+
   private ensureUniqueName(name: string): string {
     if (name.length <= 64) {
       return name;
@@ -0,0 +1,61 @@
+import { createHash } from "node:crypto";
+import fs from "node:fs";
+import { OpenAPIV3 } from "openapi-types";
+import packageJson from "../../package.json";
+import { ConverterOptions, OpenAPIToMCPConverter } from "./parser";
+
+export type ConvertedTools = Omit<ReturnType<OpenAPIToMCPConverter["convertToMCPTools"]>, "zip">;
+
+type CacheFile = ConvertedTools & {
+  key: string;
+};
+
+// Changes whenever the spec or the options that shape the conversion do, and with every release, as
+// the converter itself may have changed
+function cacheKey(openApiSpec: OpenAPIV3.Document, options: ConverterOptions): string {
+  const version = packageJson.version;
+  return createHash("sha256").update(JSON.stringify({ version, openApiSpec, options })).digest("hex");
+}
+
+function readCache(cachePath: string, key: string): ConvertedTools | undefined {
+  try {
+    const cached: CacheFile = JSON.parse(fs.readFileSync(cachePath, "utf-8"));
+    if (cached.key !== key) {
+      console.error("Tool cache is stale, converting the spec again");
+      return undefined;
+    }
+    return { tools: cached.tools, openApiLookup: cached.openApiLookup, skipped: cached.skipped };
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "ENOENT") {
+      console.error("Failed to read tool cache:", error instanceof Error ? error.message : error);
+    }
+    return undefined;
+  }
+}
+
+/**
+ * Convert a spec to MCP tools, reusing the result stored in cachePath when it was converted from
+ * the same spec with the same options by the same version. Without a cachePath the spec is always converted.
+ */
+export function convertWithCache(
+  openApiSpec: OpenAPIV3.Document,
+  options: ConverterOptions,
+  cachePath?: string,
+): ConvertedTools {
+  const key = cachePath ? cacheKey(openApiSpec, options) : "";
+  const cached = cachePath ? readCache(cachePath, key) : undefined;
+  if (cached) {
+    return cached;
+  }
+
+  const { tools, openApiLookup, skipped } = new OpenAPIToMCPConverter(openApiSpec, options).convertToMCPTools();
+  if (cachePath) {
+    try {
+      const cache: CacheFile = { key, tools, openApiLookup, skipped };
+      fs.writeFileSync(cachePath, JSON.stringify(cache));
+    } catch (error) {
+      console.error("Failed to write tool cache:", error instanceof Error ? error.message : error);
+    }
+  }
+  return { tools, openApiLookup, skipped };
+}